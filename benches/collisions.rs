@@ -0,0 +1,119 @@
+//! Compares the spatial hash broad phase against checking every pair.
+//! Run with `cargo bench --bench collisions`
+
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
+
+use bevy::{ecs::system::RunSystemOnce, prelude::*};
+use rand::prelude::*;
+
+#[allow(dead_code)]
+#[path = "../src/physics.rs"]
+mod physics;
+
+use physics::*;
+
+const WIDTH: f32 = 600.;
+const HEIGHT: f32 = 350.;
+const TICKS: u32 = 20;
+const PROJECTILE_COUNTS: [u32; 5] = [250, 500, 1000, 2000, 4000];
+
+fn main() {
+	println!(
+		"{:>12} {:>16} {:>16} {:>10}",
+		"projectiles", "spatial hash", "all pairs", "speedup"
+	);
+	for count in PROJECTILE_COUNTS {
+		check_same_pairs(count);
+		let spatial_hash = time_ticks(count, |world| {
+			world.run_system_once(update_spatial_hash).unwrap();
+			world.run_system_once(check_collisions).unwrap();
+		});
+		let all_pairs = time_ticks(count, |world| {
+			std::hint::black_box(world.run_system_once(check_collisions_all_pairs).unwrap());
+		});
+		println!(
+			"{:>12} {:>13.3}ms {:>13.3}ms {:>9.1}x",
+			count,
+			spatial_hash.as_secs_f64() * 1000.,
+			all_pairs.as_secs_f64() * 1000.,
+			all_pairs.as_secs_f64() / spatial_hash.as_secs_f64(),
+		);
+	}
+}
+
+/// Both broad phases have to find the same overlaps, otherwise the timings mean nothing
+fn check_same_pairs(count: u32) {
+	let mut world = projectile_world(count);
+	world.run_system_once(update_spatial_hash).unwrap();
+	let spatial_hash = world
+		.resource::<SpatialHash>()
+		.candidate_pairs()
+		.filter(|(a, b)| a.center.distance(b.center) < a.radius + b.radius)
+		.map(|(a, b)| ordered_pair(a.entity, b.entity))
+		.collect::<HashSet<_>>();
+	let all_pairs = world.run_system_once(check_collisions_all_pairs).unwrap();
+	assert_eq!(
+		spatial_hash, all_pairs,
+		"broad phase found different pairs with {count} projectiles"
+	);
+}
+
+fn ordered_pair(a: Entity, b: Entity) -> (Entity, Entity) {
+	if a < b { (a, b) } else { (b, a) }
+}
+
+/// Projectiles scattered over the whole arena
+fn projectile_world(count: u32) -> World {
+	let mut world = World::new();
+	world.init_resource::<Time>();
+	world.init_resource::<SpatialHash>();
+	world.init_resource::<Contacts>();
+	world.init_resource::<Events<CollisionStarted>>();
+	world.init_resource::<Events<CollisionEnded>>();
+
+	let mut rng = StdRng::seed_from_u64(42);
+	for _ in 0..count {
+		world.spawn((
+			Transform::from_xyz(
+				rng.random_range(-WIDTH..WIDTH),
+				rng.random_range(-HEIGHT..HEIGHT),
+				0.,
+			),
+			Collider {
+				radius: 20.,
+				offset: Vec2::ZERO,
+				..default()
+			},
+			PhysicsBody::default(),
+			AreaTrigger,
+		));
+	}
+	world
+}
+
+/// Average time of a tick
+fn time_ticks(count: u32, mut tick: impl FnMut(&mut World)) -> Duration {
+	let mut world = projectile_world(count);
+	let start = Instant::now();
+	for _ in 0..TICKS {
+		tick(&mut world);
+		world.resource_mut::<Events<CollisionStarted>>().update();
+		world.resource_mut::<Events<CollisionEnded>>().update();
+	}
+	start.elapsed() / TICKS
+}
+
+/// What `check_collisions` used to do before the broad phase. Returns the overlapping pairs
+fn check_collisions_all_pairs(
+	query: Query<(Entity, &Collider, &Transform)>,
+) -> HashSet<(Entity, Entity)> {
+	let mut overlaps = HashSet::new();
+	for [e1, e2] in query.iter_combinations() {
+		let axis = (e1.2.translation.xy() + e1.1.offset) - (e2.2.translation.xy() + e2.1.offset);
+		if axis.length() < e1.1.radius + e2.1.radius {
+			overlaps.insert(ordered_pair(e1.0, e2.0));
+		}
+	}
+	overlaps
+}
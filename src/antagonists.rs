@@ -0,0 +1,201 @@
+use std::time::Duration;
+
+use crate::*;
+
+/// Shoots rumors at the traders. There can be several, each following its own `AntagonistKind`
+#[derive(Component)]
+#[require(TimedShooter)]
+pub struct Antagonist {
+	pub kind: &'static AntagonistKind,
+	/// Index into `kind.phases`
+	pub phase: usize,
+	/// Seconds the condition of the current phase has held in a row
+	pub condition_held: f32,
+}
+
+impl Antagonist {
+	pub fn new(kind: &'static AntagonistKind) -> Self {
+		Self {
+			kind,
+			phase: 0,
+			condition_held: 0.,
+		}
+	}
+
+	pub fn current_phase(&self) -> &'static BossPhase {
+		&self.kind.phases[self.phase]
+	}
+}
+
+pub struct AntagonistKind {
+	pub name: &'static str,
+	/// Picked again after every shot
+	pub sprite: fn() -> String,
+	pub tint: Color,
+	/// Volume and path of each line
+	pub voice_lines: &'static [(f32, &'static str)],
+	/// Overhead text after every shot
+	pub shout: fn() -> String,
+	/// Starts with the first one every round
+	pub phases: &'static [BossPhase],
+	/// Only joins rounds of this difficulty or harder
+	pub min_difficulty: Difficulty,
+}
+
+pub struct BossPhase {
+	/// Shown when the phase starts
+	pub name: &'static str,
+	pub shot_types: &'static [Rumor],
+	pub steering: &'static [fn() -> ProjectileSteering],
+	/// Multiplier of the shooting interval of the difficulty
+	pub interval_factor: f32,
+	/// Moves on to the next phase once this is met. None for the last phase
+	pub until: Option<PhaseCondition>,
+}
+
+#[derive(Clone, Copy)]
+pub enum PhaseCondition {
+	/// At least `share` of the traders stay bullish for `seconds`
+	MarketBullish { share: f32, seconds: f32 },
+	/// Fewer seconds are left in the round
+	TimeRemaining(f32),
+}
+
+pub const DONNIE: AntagonistKind = AntagonistKind {
+	name: "Donnie",
+	sprite: donnie_texture_path,
+	tint: Color::WHITE,
+	voice_lines: &DONNIE_VOICE_LINES,
+	shout: random_tariff,
+	phases: &[
+		BossPhase {
+			name: "TARIFFS!",
+			// mostly tariffs
+			shot_types: &[
+				Rumor::Tariff,
+				Rumor::Tariff,
+				Rumor::Tariff,
+				Rumor::FakeNews,
+				Rumor::Bailout,
+			],
+			// harder to dodge
+			steering: &[ProjectileSteering::weaving, ProjectileSteering::boomerang],
+			interval_factor: 1.,
+			until: Some(PhaseCondition::MarketBullish {
+				share: 0.6,
+				seconds: 10.,
+			}),
+		},
+		BossPhase {
+			name: "TRADE WAR!",
+			shot_types: &[Rumor::Tariff],
+			steering: &[
+				ProjectileSteering::homing,
+				ProjectileSteering::weaving,
+				ProjectileSteering::boomerang,
+			],
+			interval_factor: 0.6,
+			until: Some(PhaseCondition::TimeRemaining(15.)),
+		},
+		BossPhase {
+			name: "FAKE NEWS!",
+			shot_types: &[Rumor::FakeNews, Rumor::FakeNews, Rumor::Tariff],
+			steering: &[ProjectileSteering::weaving],
+			interval_factor: 0.5,
+			until: None,
+		},
+	],
+	min_difficulty: Difficulty::Easy,
+};
+
+pub const SHORT_SELLER: AntagonistKind = AntagonistKind {
+	name: "Short Seller",
+	sprite: short_seller_texture_path,
+	tint: Color::WHITE,
+	voice_lines: &SHORT_SELLER_VOICE_LINES,
+	shout: random_short_sell,
+	phases: &[
+		BossPhase {
+			name: "SELL!",
+			shot_types: &[Rumor::FakeNews, Rumor::Bailout],
+			steering: &[],
+			interval_factor: 1.5,
+			until: Some(PhaseCondition::MarketBullish {
+				share: 0.75,
+				seconds: 8.,
+			}),
+		},
+		BossPhase {
+			name: "SHORT SQUEEZE!",
+			shot_types: &[Rumor::FakeNews, Rumor::FakeNews, Rumor::Tariff],
+			steering: &[ProjectileSteering::homing],
+			interval_factor: 1.,
+			until: None,
+		},
+	],
+	min_difficulty: Difficulty::Hard,
+};
+
+/// Spawned left to right along the top, see `Difficulty::antagonists`
+pub const ANTAGONISTS: [&AntagonistKind; 2] = [&DONNIE, &SHORT_SELLER];
+
+/// Loads the shot types and steering of `phase` into the shooter
+pub fn set_phase(
+	antagonist: &mut Antagonist,
+	shooter: &mut TimedShooter,
+	phase: usize,
+	difficulty: Difficulty,
+) {
+	antagonist.phase = phase;
+	antagonist.condition_held = 0.;
+	let phase = antagonist.current_phase();
+	shooter.shot_types = phase.shot_types.to_vec();
+	shooter.steering = phase.steering.iter().map(|steering| steering()).collect();
+	shooter.targeting = difficulty.targeting();
+	shooter.shooting_timer.set_duration(Duration::from_secs_f32(
+		difficulty.shooting_interval() * phase.interval_factor,
+	));
+}
+
+pub fn advance_boss_phases(
+	mut antagonists: Query<(Entity, &mut Antagonist, &mut TimedShooter)>,
+	traders: Query<&Trader>,
+	stats: Res<GameStats>,
+	difficulty: Res<Difficulty>,
+	time: Res<Time>,
+	mut overhead_events: EventWriter<OverheadTextRequest>,
+) {
+	let bullish_share = traders
+		.iter()
+		.filter(|t| t.status == TraderStatus::Bullish)
+		.count() as f32
+		/ traders.iter().len().max(1) as f32;
+	for (entity, mut antagonist, mut shooter) in antagonists.iter_mut() {
+		let Some(condition) = antagonist.current_phase().until else {
+			continue;
+		};
+		let met = match condition {
+			PhaseCondition::MarketBullish { share, seconds } => {
+				if bullish_share >= share {
+					antagonist.condition_held += time.delta_secs();
+				} else {
+					antagonist.condition_held = 0.;
+				}
+				antagonist.condition_held >= seconds
+			}
+			PhaseCondition::TimeRemaining(seconds) => {
+				stats.time_remaining.remaining_secs() < seconds
+			}
+		};
+		if !met {
+			continue;
+		}
+		let next = antagonist.phase + 1;
+		set_phase(&mut antagonist, &mut shooter, next, *difficulty);
+		overhead_events.write(OverheadTextRequest {
+			attached_to: entity,
+			text: Some(antagonist.current_phase().name.to_string()),
+			duration_sec: Some(2.),
+		});
+	}
+}
@@ -0,0 +1,125 @@
+use crate::*;
+
+/// Personality of a trader. Key into `ARCHETYPE_KINDS`
+#[derive(Component, PartialEq, Eq, Clone, Copy)]
+pub enum Archetype {
+	Regular,
+	/// Stubborn and slow, moves the price a lot
+	Whale,
+	/// Fast and changes its mind quickly
+	DayTrader,
+	/// Spreads rumors far
+	Influencer,
+}
+
+pub struct ArchetypeKind {
+	pub name: &'static str,
+	pub sprites: TraderSprites,
+	pub tint: Color,
+	/// Multiplier of the sprite and collider size
+	pub size: f32,
+	/// Rumors needed before the status changes
+	pub hits_required: u32,
+	/// Multiplier of the random movement velocity
+	pub speed: f32,
+	/// How much the trader counts in the stonks price, relative to the others
+	pub price_weight: u32,
+	/// Multiplier of the status duration of rumors
+	pub status_duration: f32,
+	/// How strongly its mood rubs off on neighbours, see `Contagion`
+	pub influence: f32,
+	/// Traders this close are converted along with it. 0 for none
+	pub spread_radius: f32,
+	/// Moves with the herd (`Flocking`) instead of on its own (`RandomMovement`) when `Herding` is on
+	pub flocks: bool,
+	/// None picks one of `TRADER_SPLIT_PATTERNS`
+	pub split_pattern: Option<SplitPattern>,
+	/// Relative chance of being spawned
+	pub spawn_weight: u32,
+}
+
+/// Same order as the `Archetype` variants
+const ARCHETYPE_KINDS: [ArchetypeKind; 4] = [
+	ArchetypeKind {
+		name: "Regular",
+		sprites: INVESTOR_SPRITES,
+		tint: Color::WHITE,
+		size: 1.,
+		hits_required: 1,
+		speed: 1.,
+		price_weight: 1,
+		status_duration: 1.,
+		influence: 1.,
+		spread_radius: 0.,
+		flocks: true,
+		split_pattern: None,
+		spawn_weight: 6,
+	},
+	ArchetypeKind {
+		name: "Whale",
+		sprites: WHALE_SPRITES,
+		tint: Color::srgb(0.7, 0.8, 1.),
+		size: 1.4,
+		hits_required: 2,
+		speed: 0.5,
+		price_weight: 3,
+		status_duration: 1.5,
+		influence: 1.,
+		spread_radius: 0.,
+		// too big to follow anyone
+		flocks: false,
+		split_pattern: None,
+		spawn_weight: 1,
+	},
+	ArchetypeKind {
+		name: "Day Trader",
+		sprites: DAY_TRADER_SPRITES,
+		tint: Color::srgb(1., 1., 0.7),
+		size: 0.8,
+		hits_required: 1,
+		speed: 1.8,
+		price_weight: 1,
+		status_duration: 0.5,
+		influence: 0.5,
+		spread_radius: 0.,
+		flocks: false,
+		split_pattern: None,
+		spawn_weight: 2,
+	},
+	ArchetypeKind {
+		name: "Influencer",
+		sprites: INFLUENCER_SPRITES,
+		tint: Color::srgb(1., 0.75, 1.),
+		size: 1.,
+		hits_required: 1,
+		speed: 1.,
+		price_weight: 1,
+		status_duration: 1.,
+		influence: 3.,
+		spread_radius: 90.,
+		flocks: true,
+		split_pattern: Some(SplitPattern::Uniform(UniformPattern { bullet_count: 6 })),
+		spawn_weight: 1,
+	},
+];
+
+pub const ARCHETYPES: [Archetype; 4] = [
+	Archetype::Regular,
+	Archetype::Whale,
+	Archetype::DayTrader,
+	Archetype::Influencer,
+];
+
+impl Archetype {
+	pub fn kind(self) -> &'static ArchetypeKind {
+		&ARCHETYPE_KINDS[self as usize]
+	}
+
+	/// Weighted by `spawn_weight`
+	pub fn random() -> Self {
+		use rand::seq::IndexedRandom;
+		*ARCHETYPES
+			.choose_weighted(&mut rand::rng(), |a| a.kind().spawn_weight)
+			.unwrap()
+	}
+}
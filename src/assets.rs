@@ -4,6 +4,10 @@ pub fn donnie_texture_path() -> String {
 	format!("taco_man3/donnie{}.PNG", rand::random_range(1..=6))
 }
 
+pub fn short_seller_texture_path() -> String {
+	"taco_man3/short_seller.PNG".to_string()
+}
+
 pub fn investor_texture_path() -> String {
 	format!("taco_man3/investor{}.PNG", rand::random_range(1..=2))
 }
@@ -16,6 +20,84 @@ pub fn bearish_texture_path() -> String {
 	format!("taco_man3/bearish{}.PNG", rand::random_range(1..=2))
 }
 
+// special archetypes have a single look each, dressed in their own color
+pub fn whale_texture_path() -> String {
+	"taco_man3/whale.PNG".to_string()
+}
+
+pub fn whale_bullish_texture_path() -> String {
+	"taco_man3/whale_bullish.PNG".to_string()
+}
+
+pub fn whale_bearish_texture_path() -> String {
+	"taco_man3/whale_bearish.PNG".to_string()
+}
+
+pub fn day_trader_texture_path() -> String {
+	"taco_man3/day_trader.PNG".to_string()
+}
+
+pub fn day_trader_bullish_texture_path() -> String {
+	"taco_man3/day_trader_bullish.PNG".to_string()
+}
+
+pub fn day_trader_bearish_texture_path() -> String {
+	"taco_man3/day_trader_bearish.PNG".to_string()
+}
+
+pub fn influencer_texture_path() -> String {
+	"taco_man3/influencer.PNG".to_string()
+}
+
+pub fn influencer_bullish_texture_path() -> String {
+	"taco_man3/influencer_bullish.PNG".to_string()
+}
+
+pub fn influencer_bearish_texture_path() -> String {
+	"taco_man3/influencer_bearish.PNG".to_string()
+}
+
+/// Texture path helpers of a trader for each status
+pub struct TraderSprites {
+	pub neutral: fn() -> String,
+	pub bullish: fn() -> String,
+	pub bearish: fn() -> String,
+}
+
+impl TraderSprites {
+	pub fn path(&self, status: TraderStatus) -> String {
+		match status {
+			TraderStatus::Neutral => (self.neutral)(),
+			TraderStatus::Bullish => (self.bullish)(),
+			TraderStatus::Bearish => (self.bearish)(),
+		}
+	}
+}
+
+pub const INVESTOR_SPRITES: TraderSprites = TraderSprites {
+	neutral: investor_texture_path,
+	bullish: bullish_texture_path,
+	bearish: bearish_texture_path,
+};
+
+pub const WHALE_SPRITES: TraderSprites = TraderSprites {
+	neutral: whale_texture_path,
+	bullish: whale_bullish_texture_path,
+	bearish: whale_bearish_texture_path,
+};
+
+pub const DAY_TRADER_SPRITES: TraderSprites = TraderSprites {
+	neutral: day_trader_texture_path,
+	bullish: day_trader_bullish_texture_path,
+	bearish: day_trader_bearish_texture_path,
+};
+
+pub const INFLUENCER_SPRITES: TraderSprites = TraderSprites {
+	neutral: influencer_texture_path,
+	bullish: influencer_bullish_texture_path,
+	bearish: influencer_bearish_texture_path,
+};
+
 pub const FONT_MAIN: &str = "fonts/Funicorn.ttf";
 // pub const FONT_SECONDARY: &str = "fonts/RelayfunDEMO.ttf";
 
@@ -75,8 +75,9 @@ pub fn setup_audio(
 	));
 }
 
-pub fn on_donnie_shot(
-	_: Trigger<RumorJustShot>,
+pub fn on_antagonist_shot(
+	trigger: Trigger<RumorJustShot>,
+	antagonists: Query<&Antagonist>,
 	mut cmds: Commands,
 	asset_server: ResMut<AssetServer>,
 	audio_counters: Res<AudioLimitCounters>,
@@ -86,7 +87,10 @@ pub fn on_donnie_shot(
 	if audio_counters[AudioType::DonnieVoice as usize] == 0 || rand::random_bool(1. - DONNIE_LINE_CHANCE) {
 		return;
 	}
-	let (track_volume, path) = random_string(&DONNIE_VOICE_LINES);
+	let Ok(antagonist) = antagonists.get(trigger.target()) else {
+		return;
+	};
+	let (track_volume, path) = random_string(antagonist.kind.voice_lines);
 	cmds.spawn((
 		AudioPlayer::new(asset_server.load(path)),
 		PlaybackSettings {
@@ -151,6 +155,30 @@ pub fn on_projectile_shot(
 	));
 }
 
+/// Projectiles meeting mid-air, see `interactions.rs`
+pub fn on_rumor_interaction<E: Event>(
+	_: Trigger<E>,
+	asset_server: ResMut<AssetServer>,
+	mut cmds: Commands,
+	audio_counters: Res<AudioLimitCounters>,
+	volume: Res<VolumeSettings>,
+) {
+	if audio_counters[AudioType::ProjectileShot as usize] == 0 {
+		return;
+	}
+	cmds.spawn((
+		AudioPlayer::new(asset_server.load(random_string(&PLOPS))),
+		PlaybackSettings {
+			mode: PlaybackMode::Despawn,
+			volume: Volume::Linear(0.6 * volume[&AudioType::ProjectileShot]),
+			speed: 1.5,
+			..default()
+		},
+		LimitedAudio(AudioType::ProjectileShot),
+		AudioTypeMarker(AudioType::ProjectileShot),
+	));
+}
+
 pub fn on_stonks_notification(
 	trigger: Trigger<StonksPriceNotification>,
 	asset_server: ResMut<AssetServer>,
@@ -214,7 +242,7 @@ const BEARISH: [&str; 1] = ["audio/fx/423526__ccolbert70eagles23__karate-chop.m4
 const PLOPS: [&str; 1] = ["audio/fx/245645__unfa__cartoon-pop-clean.flac"];
 
 // Separate volume level for each track
-const DONNIE_VOICE_LINES: [(f32, &str); 24] = [
+pub const DONNIE_VOICE_LINES: [(f32, &str); 24] = [
 	(1.2, "audio/soundboard/Voicy_We have a president who doesn't have a clue.mp3"),
 	(1.4, "audio/soundboard/Voicy_Well i don't have to really get into specifics.mp3"),
 	(1.5, "audio/soundboard/Voicy_Don't know what there doing.mp3"),
@@ -240,3 +268,10 @@ const DONNIE_VOICE_LINES: [(f32, &str); 24] = [
 	(1.5, "audio/soundboard/Voicy_We have people that are morally corrupt we have people .mp3"),
 	(1.0, "audio/soundboard/Voicy_What I say is what I sayu.mp3"),
 ];
+
+pub const SHORT_SELLER_VOICE_LINES: [(f32, &str); 4] = [
+	(1.0, "audio/soundboard/Voicy_Small Loan of a Million Dollars.mp3"),
+	(0.9, "audio/soundboard/Voicy_The system is rigged.mp3"),
+	(1.1, "audio/soundboard/Voicy_These are corrupt people.mp3"),
+	(1.8, "audio/soundboard/Voicy_I don't give a damn.mp3"),
+];
@@ -0,0 +1,150 @@
+use crate::*;
+use bevy::platform::collections::HashMap;
+
+/// Which chain reaction a projectile belongs to, and how many hits happened before it was spawned
+#[derive(Component, Clone, Copy)]
+pub struct ChainLink {
+	pub chain: u32,
+	pub depth: u32,
+}
+
+impl ChainLink {
+	/// Link of the projectiles split off when this one hits
+	pub fn next(self) -> Self {
+		Self {
+			depth: self.depth + 1,
+			..self
+		}
+	}
+}
+
+pub struct ChainStats {
+	/// Who fired the first projectile
+	pub origin: Option<Entity>,
+	pub started_at: f32,
+	pub traders_converted: u32,
+	pub max_depth: u32,
+	live_projectiles: u32,
+}
+
+/// Chains with projectiles still flying. Completed ones are removed by `complete_chains`
+#[derive(Resource, Default)]
+pub struct ChainTracker {
+	next_id: u32,
+	chains: HashMap<u32, ChainStats>,
+}
+
+impl ChainTracker {
+	pub fn start(&mut self, origin: Option<Entity>, now: f32) -> ChainLink {
+		let chain = self.next_id;
+		self.next_id += 1;
+		self.chains.insert(
+			chain,
+			ChainStats {
+				origin,
+				started_at: now,
+				traders_converted: 0,
+				max_depth: 0,
+				live_projectiles: 0,
+			},
+		);
+		ChainLink { chain, depth: 0 }
+	}
+
+	pub fn projectile_spawned(&mut self, link: ChainLink) {
+		if let Some(stats) = self.chains.get_mut(&link.chain) {
+			stats.live_projectiles += 1;
+			stats.max_depth = stats.max_depth.max(link.depth);
+		}
+	}
+
+	pub fn projectile_released(&mut self, link: ChainLink) {
+		if let Some(stats) = self.chains.get_mut(&link.chain) {
+			stats.live_projectiles = stats.live_projectiles.saturating_sub(1);
+		}
+	}
+
+	/// Who fired the first projectile of the chain
+	pub fn origin(&self, link: ChainLink) -> Option<Entity> {
+		self.chains.get(&link.chain)?.origin
+	}
+
+	pub fn trader_converted(&mut self, link: ChainLink) {
+		if let Some(stats) = self.chains.get_mut(&link.chain) {
+			stats.traders_converted += 1;
+		}
+	}
+}
+
+/// The last projectile of a chain is gone
+#[derive(Event)]
+pub struct ChainCompleted {
+	pub origin: Option<Entity>,
+	pub traders_converted: u32,
+	pub max_depth: u32,
+	pub duration: f32,
+}
+
+/// Grows with every good chain of the player in a row
+#[derive(Resource)]
+pub struct Combo {
+	pub multiplier: u32,
+}
+
+impl Default for Combo {
+	fn default() -> Self {
+		Self { multiplier: 1 }
+	}
+}
+
+/// Has to run after `spawn_projectiles`, so children of the last hit are already counted
+pub fn complete_chains(
+	mut tracker: ResMut<ChainTracker>,
+	mut completed: EventWriter<ChainCompleted>,
+	time: Res<Time>,
+) {
+	tracker.chains.retain(|_, stats| {
+		if stats.live_projectiles > 0 {
+			return true;
+		}
+		completed.write(ChainCompleted {
+			origin: stats.origin,
+			traders_converted: stats.traders_converted,
+			max_depth: stats.max_depth,
+			duration: time.elapsed_secs() - stats.started_at,
+		});
+		false
+	});
+}
+
+pub fn score_chains(
+	mut completed: EventReader<ChainCompleted>,
+	mut combo: ResMut<Combo>,
+	mut stonks: ResMut<StonksTrading>,
+	mut effects: EventWriter<TextEffectRequest>,
+	player: Single<Entity, With<Player>>,
+) {
+	for chain in completed.read() {
+		if chain.origin != Some(*player) {
+			continue;
+		}
+		if chain.traders_converted < COMBO_MIN_CONVERSIONS {
+			combo.multiplier = 1;
+			continue;
+		}
+		let bonus = (chain.traders_converted * combo.multiplier) as i64 * COMBO_BONUS;
+		stonks.returns_total += bonus;
+		effects.write(TextEffectRequest {
+			text: format!(
+				"CHAIN OF {} x{} = {}!\n{} deep in {:.1}s",
+				chain.traders_converted,
+				combo.multiplier,
+				format_money(bonus),
+				chain.max_depth,
+				chain.duration
+			),
+			duration_sec: 1.5,
+		});
+		combo.multiplier += 1;
+	}
+}
@@ -15,22 +15,63 @@ pub const STONKS_DATA_POINTS: u32 = 300;
 pub const STONKS_PER_BUY_ACTION: u32 = 300;
 
 pub const TRADER_COUNT: u32 = 15;
-pub const PROJECTILE_SPEED: f32 = 7.;
+pub const TRADER_RADIUS: f32 = 25.;
+/// Bankruptcies stop here
+pub const MIN_TRADERS: usize = 5;
+/// IPOs stop here
+pub const MAX_TRADERS: usize = 30;
+/// Seconds of being bearish before a trader leaves the market
+pub const BANKRUPTCY_TIME: f32 = 8.;
+pub const IPO_WAVE_SIZE: usize = 3;
+/// Minimum seconds between IPO waves
+pub const IPO_COOLDOWN: f32 = 10.;
+/// Share of the price range above which IPOs happen
+pub const IPO_PRICE_RATIO: f32 = 0.8;
+/// Units per second
+pub const PROJECTILE_SPEED: f32 = 450.;
+pub const PROJECTILE_RADIUS: f32 = 20.;
+/// Seconds before a projectile is removed
+pub const PROJECTILE_LIFETIME: f32 = 5.;
+/// Units a projectile can travel before it is removed
+pub const PROJECTILE_RANGE: f32 = 2000.;
+/// Radians per second
+pub const HOMING_TURN_RATE: f32 = 3.;
+/// Radians of sway around the initial direction
+pub const WEAVE_AMPLITUDE: f32 = 0.6;
+/// Full sways per second
+pub const WEAVE_FREQUENCY: f32 = 2.;
+/// Seconds before a boomerang turns back
+pub const BOOMERANG_RETURN_TIME: f32 = 0.8;
+/// Units per second
+pub const FLOCK_MAX_SPEED: f32 = 128.;
+/// Distance at which flocking traders see each other
+pub const FLOCK_VIEW_RADIUS: f32 = 120.;
 pub const MOVEMENT_TIME: f32 = 5.;
 pub const IDLE_TIME: f32 = 1.;
 
 pub const MAX_TACOS: u32 = 3;
+/// Seconds of holding the button to charge one more taco into a shot
+pub const CHARGE_TIME_PER_TACO: f32 = 0.4;
+pub const PICKUP_SPAWN_TIME: f32 = 8.;
+pub const PICKUP_MAX_ACTIVE: usize = 2;
+/// Seconds before an uncollected pickup disappears
+pub const PICKUP_LIFETIME: f32 = 10.;
+/// Real seconds a pickup effect lasts
+pub const PICKUP_EFFECT_TIME: f32 = 6.;
+pub const PICKUP_MAX_TACOS_BOOST: u32 = 2;
+pub const SLOW_MOTION_SPEED: f32 = 0.5;
+/// Traders a chain has to convert to grow the combo
+pub const COMBO_MIN_CONVERSIONS: u32 = 3;
+/// Dollars added to the returns per converted trader, times the combo multiplier
+pub const COMBO_BONUS: i64 = 100;
 pub const TACO_CHARGE_TIME: f32 = 1.;
 
 pub const DONNIE_LINE_CHANCE: f64 = 0.5;
 pub const DONNIE_LIE_CHANCE: f64 = 1.;
 
 pub fn get_trader_random_velocity() -> Vec2 {
-	const TRADER_MAX_VELOCITY: f32 = 2.0;
+	// units per second
+	const TRADER_MAX_VELOCITY: f32 = 128.;
 	let angle = rand::random_range(0.0..PI) * 2.;
 	Vec2::new(angle.cos(), angle.sin()) * rand::random_range(0.5..1.0) * TRADER_MAX_VELOCITY
 }
-
-// computed from above
-pub const PRICE_LOWEST: f32 = (STONKS_PER_BEARISH * TRADER_COUNT) as f32;
-pub const PRICE_HIGHEST: f32 = (STONKS_PER_BULLISH * TRADER_COUNT) as f32;
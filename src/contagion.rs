@@ -0,0 +1,125 @@
+use crate::*;
+
+/// Traders that hang around bullish or bearish traders slowly catch their mood
+#[derive(Resource)]
+pub struct Contagion {
+	pub enabled: bool,
+	/// Distance at which a trader feels its neighbours
+	pub radius: f32,
+	/// Seconds next to a single neighbour before the status moves one step towards it
+	pub time_to_convert: f32,
+	/// Seconds a caught status lasts
+	pub status_duration: f32,
+}
+
+impl Default for Contagion {
+	fn default() -> Self {
+		Self {
+			enabled: false,
+			radius: 80.,
+			time_to_convert: 3.,
+			status_duration: 4.,
+		}
+	}
+}
+
+/// Positive drifts towards bullish, negative towards bearish
+#[derive(Component, Default)]
+pub struct SentimentExposure(pub f32);
+
+pub fn spread_sentiment(
+	contagion: Res<Contagion>,
+	spatial_hash: Res<SpatialHash>,
+	mut traders: Query<(
+		Entity,
+		&mut Trader,
+		&mut SentimentExposure,
+		&Transform,
+		&Archetype,
+		Has<StatusLock>,
+	)>,
+	mut trader_changes: EventWriter<TraderChange>,
+	time: Res<Time>,
+	mut cmds: Commands,
+) {
+	if !contagion.enabled {
+		return;
+	}
+	// read every mood before changing any, so the order doesn't matter
+	let pressures = traders
+		.iter()
+		.map(|(entity, _, _, transform, _, _)| {
+			let pressure = spatial_hash
+				.query_circle(
+					transform.translation.xy(),
+					contagion.radius,
+					CollisionLayer::TRADER,
+				)
+				.filter(|neighbour| neighbour.entity != entity)
+				.filter_map(|neighbour| traders.get(neighbour.entity).ok())
+				.map(|(_, trader, _, _, archetype, _)| {
+					let influence = archetype.kind().influence;
+					match trader.status {
+						TraderStatus::Bullish => influence,
+						TraderStatus::Bearish => -influence,
+						TraderStatus::Neutral => 0.,
+					}
+				})
+				.sum::<f32>();
+			(entity, pressure)
+		})
+		.collect::<Vec<_>>();
+
+	for (entity, pressure) in pressures {
+		let (_, mut trader, mut exposure, _, _, locked) = traders.get_mut(entity).unwrap();
+		if locked {
+			exposure.0 = 0.;
+			continue;
+		}
+		if pressure == 0. {
+			// calms down when left alone
+			exposure.0 = (exposure.0.abs() - time.delta_secs()).max(0.) * exposure.0.signum();
+			continue;
+		}
+		exposure.0 += pressure * time.delta_secs();
+		let new_status = if exposure.0 >= contagion.time_to_convert {
+			match trader.status {
+				TraderStatus::Bearish => TraderStatus::Neutral,
+				_ => TraderStatus::Bullish,
+			}
+		} else if exposure.0 <= -contagion.time_to_convert {
+			match trader.status {
+				TraderStatus::Bullish => TraderStatus::Neutral,
+				_ => TraderStatus::Bearish,
+			}
+		} else {
+			continue;
+		};
+		exposure.0 = 0.;
+		if new_status == trader.status {
+			continue;
+		}
+		trader_changes.write(TraderChange {
+			entity,
+			prev: trader.status,
+			new: new_status,
+		});
+		trader.status = new_status;
+		trader.hits = 0;
+		if new_status == TraderStatus::Neutral {
+			cmds.entity(entity).remove::<TraderStatusTimer>();
+		} else {
+			cmds.entity(entity)
+				.insert(TraderStatusTimer(Timer::from_seconds(
+					contagion.status_duration,
+					TimerMode::Once,
+				)));
+		}
+	}
+}
+
+pub fn set_contagion(enabled: bool) -> impl Fn(Trigger<Pointer<Click>>, ResMut<Contagion>) {
+	move |_: Trigger<Pointer<Click>>, mut contagion: ResMut<Contagion>| {
+		contagion.enabled = enabled;
+	}
+}
@@ -28,6 +28,20 @@ pub const BEARISH: [&str; 11] = [
 	"MY 401K",
 ];
 
+pub const CANCELLED: [&str; 4] = ["DEBUNKED", "FACT CHECKED", "CANCELLED", "POOF"];
+
+pub const MERGED: [&str; 4] = ["DOUBLE", "MEGA", "STACKED", "RECIPROCAL"];
+
+pub const DEFLECTED: [&str; 4] = ["DENIED", "NOPE", "BOUNCED", "NOT TODAY"];
+
+pub const SHORT_SELL_LINES: [&str; 5] = [
+	"SELL SELL\nSELL",
+	"OVERVALUED",
+	"BUBBLE!",
+	"SHORT IT",
+	"PUTS ON\nEVERYTHING",
+];
+
 pub const TARIFF_VALUES: [&str; 10] = [
 	"20",
 	"42",
@@ -91,6 +105,10 @@ pub fn random_tariff() -> String {
 	format!("{}% TARIFFS\nON {}", value, target)
 }
 
+pub fn random_short_sell() -> String {
+	random_string(&SHORT_SELL_LINES).to_string()
+}
+
 #[derive(Event)]
 pub struct OverheadTextRequest {
 	pub attached_to: Entity,
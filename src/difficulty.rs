@@ -0,0 +1,190 @@
+use crate::*;
+
+/// Picked in the options menu, applied when a round starts
+#[derive(Resource, Clone, Copy, PartialEq, PartialOrd, Default)]
+pub enum Difficulty {
+	Easy,
+	#[default]
+	Normal,
+	Hard,
+}
+
+impl Difficulty {
+	/// Antagonists of a round, left to right
+	pub fn antagonists(self) -> Vec<&'static AntagonistKind> {
+		ANTAGONISTS
+			.into_iter()
+			.filter(|kind| self >= kind.min_difficulty)
+			.collect()
+	}
+
+	/// For antagonists. One is picked at random for every shot
+	pub fn targeting(self) -> Vec<TargetingStrategy> {
+		match self {
+			Difficulty::Easy => vec![TargetingStrategy::Random],
+			Difficulty::Normal => vec![TargetingStrategy::Random, TargetingStrategy::Nearest],
+			Difficulty::Hard => vec![
+				TargetingStrategy::Lead,
+				TargetingStrategy::BullishCluster,
+				TargetingStrategy::NearPlayer,
+			],
+		}
+	}
+
+	/// Seconds between antagonist shots, before the factor of their boss phase
+	pub fn shooting_interval(self) -> f32 {
+		match self {
+			Difficulty::Easy => 2.5,
+			Difficulty::Normal => 2.,
+			Difficulty::Hard => 1.5,
+		}
+	}
+
+	pub fn curve(self) -> DifficultyCurve {
+		match self {
+			Difficulty::Easy => DifficultyCurve {
+				points: vec![
+					(0., DifficultyScale::default()),
+					(
+						1.,
+						DifficultyScale {
+							fire_rate: 1.5,
+							..default()
+						},
+					),
+				],
+			},
+			Difficulty::Normal => DifficultyCurve::default(),
+			Difficulty::Hard => DifficultyCurve {
+				points: vec![
+					(
+						0.,
+						DifficultyScale {
+							fire_rate: 1.2,
+							..default()
+						},
+					),
+					(
+						0.5,
+						DifficultyScale {
+							fire_rate: 1.8,
+							projectile_speed: 1.3,
+							burst_size: 2.,
+							status_duration: 1.3,
+						},
+					),
+					(
+						1.,
+						DifficultyScale {
+							fire_rate: 2.5,
+							projectile_speed: 1.5,
+							burst_size: 3.,
+							status_duration: 1.6,
+						},
+					),
+				],
+			},
+		}
+	}
+}
+
+/// Multipliers applied to shooters other than the player
+#[derive(Clone, Copy)]
+pub struct DifficultyScale {
+	pub fire_rate: f32,
+	pub projectile_speed: f32,
+	/// Shots per trigger, rounded down
+	pub burst_size: f32,
+	/// Of the statuses their rumors cause
+	pub status_duration: f32,
+}
+
+impl Default for DifficultyScale {
+	fn default() -> Self {
+		Self {
+			fire_rate: 1.,
+			projectile_speed: 1.,
+			burst_size: 1.,
+			status_duration: 1.,
+		}
+	}
+}
+
+impl DifficultyScale {
+	fn lerp(self, other: Self, t: f32) -> Self {
+		Self {
+			fire_rate: self.fire_rate.lerp(other.fire_rate, t),
+			projectile_speed: self.projectile_speed.lerp(other.projectile_speed, t),
+			burst_size: self.burst_size.lerp(other.burst_size, t),
+			status_duration: self.status_duration.lerp(other.status_duration, t),
+		}
+	}
+
+	pub fn burst(self) -> u32 {
+		(self.burst_size as u32).max(1)
+	}
+}
+
+/// Scale over the round. Points are (fraction of `ROUND_TIME` elapsed, scale), sorted by time
+#[derive(Resource, Clone)]
+pub struct DifficultyCurve {
+	pub points: Vec<(f32, DifficultyScale)>,
+}
+
+impl Default for DifficultyCurve {
+	fn default() -> Self {
+		Self {
+			points: vec![
+				(0., DifficultyScale::default()),
+				(
+					1.,
+					DifficultyScale {
+						fire_rate: 2.,
+						projectile_speed: 1.25,
+						burst_size: 2.,
+						status_duration: 1.3,
+					},
+				),
+			],
+		}
+	}
+}
+
+impl DifficultyCurve {
+	/// Linear between the points, flat before the first and after the last
+	pub fn sample(&self, progress: f32) -> DifficultyScale {
+		let Some(next) = self.points.iter().position(|(t, _)| *t > progress) else {
+			return self.points.last().map(|p| p.1).unwrap_or_default();
+		};
+		if next == 0 {
+			return self.points[0].1;
+		}
+		let (t0, a) = self.points[next - 1];
+		let (t1, b) = self.points[next];
+		a.lerp(b, (progress - t0) / (t1 - t0))
+	}
+
+	pub fn at(&self, stats: &GameStats) -> DifficultyScale {
+		self.sample(stats.time_remaining.fraction())
+	}
+}
+
+/// Also puts every antagonist back to its first boss phase
+pub fn apply_difficulty(
+	difficulty: Res<Difficulty>,
+	mut antagonists: Query<(&mut Antagonist, &mut TimedShooter)>,
+	mut cmds: Commands,
+) {
+	cmds.insert_resource(difficulty.curve());
+	for (mut antagonist, mut shooter) in antagonists.iter_mut() {
+		set_phase(&mut antagonist, &mut shooter, 0, *difficulty);
+	}
+}
+
+pub fn set_difficulty(
+	difficulty: Difficulty,
+) -> impl Fn(Trigger<Pointer<Click>>, ResMut<Difficulty>) {
+	move |_: Trigger<Pointer<Click>>, mut res: ResMut<Difficulty>| {
+		*res = difficulty;
+	}
+}
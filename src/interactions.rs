@@ -0,0 +1,220 @@
+use crate::*;
+use bevy::platform::collections::HashSet;
+
+/// What happens when two rumors meet mid-air
+#[derive(Clone, Copy, PartialEq)]
+pub enum ProjectileInteraction {
+	/// Both are removed
+	Cancel,
+	/// The second is absorbed by the first, which grows
+	Merge,
+	/// The second bounces off the first
+	Deflect,
+}
+
+/// Pairs of rumors that interact, checked in both orders. Anything else passes through
+#[derive(Resource)]
+pub struct InteractionRules {
+	pub rules: Vec<(Rumor, Rumor, ProjectileInteraction)>,
+}
+
+impl Default for InteractionRules {
+	fn default() -> Self {
+		Self {
+			rules: vec![
+				(Rumor::Taco, Rumor::Tariff, ProjectileInteraction::Cancel),
+				(Rumor::Tariff, Rumor::Tariff, ProjectileInteraction::Merge),
+				(
+					Rumor::InsiderTip,
+					Rumor::Tariff,
+					ProjectileInteraction::Deflect,
+				),
+			],
+		}
+	}
+}
+
+impl InteractionRules {
+	/// Also tells if the rule was written for `b` first
+	fn find(&self, a: Rumor, b: Rumor) -> Option<(ProjectileInteraction, bool)> {
+		self.rules.iter().find_map(|&(first, second, rule)| {
+			if (first, second) == (a, b) {
+				Some((rule, false))
+			} else if (first, second) == (b, a) {
+				Some((rule, true))
+			} else {
+				None
+			}
+		})
+	}
+}
+
+#[derive(Event)]
+pub struct RumorsCancelled {
+	pub position: Vec2,
+	pub rumors: [Rumor; 2],
+}
+
+#[derive(Event)]
+pub struct RumorsMerged {
+	pub position: Vec2,
+	pub rumor: Rumor,
+}
+
+#[derive(Event)]
+pub struct RumorDeflected {
+	pub position: Vec2,
+	/// The one that bounced
+	pub rumor: Rumor,
+}
+
+/// Merged projectiles stop growing at this radius
+const MAX_MERGED_RADIUS: f32 = PROJECTILE_RADIUS * 3.;
+
+pub fn handle_projectile_interactions(
+	mut collisions: EventReader<CollisionStarted>,
+	rules: Res<InteractionRules>,
+	mut projectiles: Query<
+		(
+			&Rumor,
+			&ChainLink,
+			&Transform,
+			&mut PhysicsBody,
+			&mut Collider,
+			&mut Sprite,
+		),
+		With<Projectile>,
+	>,
+	mut cmds: Commands,
+) {
+	// released projectiles are still around until the commands are applied
+	let mut removed = HashSet::new();
+	for collision in collisions.read() {
+		let Some((mut first, mut second)) =
+			collision.between(CollisionLayer::PROJECTILE, CollisionLayer::PROJECTILE)
+		else {
+			continue;
+		};
+		if removed.contains(&first) || removed.contains(&second) {
+			continue;
+		}
+		let Ok([a, b]) = projectiles.get_many([first, second]) else {
+			continue;
+		};
+		// siblings of the same split (or burst) start on top of each other
+		if a.1.chain == b.1.chain && a.1.depth == b.1.depth {
+			continue;
+		}
+		let Some((interaction, swapped)) = rules.find(*a.0, *b.0) else {
+			continue;
+		};
+		if swapped {
+			std::mem::swap(&mut first, &mut second);
+		}
+
+		let [mut a, mut b] = projectiles.get_many_mut([first, second]).unwrap();
+		let position = (a.2.translation.xy() + b.2.translation.xy()) / 2.;
+		match interaction {
+			ProjectileInteraction::Cancel => {
+				cmds.entity(first).queue(release_projectile);
+				cmds.entity(second).queue(release_projectile);
+				removed.extend([first, second]);
+				cmds.trigger(RumorsCancelled {
+					position,
+					rumors: [*a.0, *b.0],
+				});
+			}
+			ProjectileInteraction::Merge => {
+				let speed = a.3.velocity.length();
+				a.3.velocity = (a.3.velocity + b.3.velocity).normalize_or(a.3.velocity) * speed;
+				a.4.radius = a.4.radius.hypot(b.4.radius).min(MAX_MERGED_RADIUS);
+				a.5.custom_size = Some(Vec2::splat(50. * a.4.radius / PROJECTILE_RADIUS));
+				cmds.entity(second).queue(release_projectile);
+				removed.insert(second);
+				cmds.trigger(RumorsMerged {
+					position,
+					rumor: *a.0,
+				});
+			}
+			ProjectileInteraction::Deflect => {
+				let normal = (b.2.translation.xy() - a.2.translation.xy())
+					.normalize_or(-b.3.velocity.normalize_or_zero());
+				// only when moving towards each other
+				let relative_velocity = b.3.velocity - a.3.velocity;
+				if relative_velocity.dot(normal) < 0. {
+					b.3.velocity = b.3.velocity.reflect(normal);
+				}
+				cmds.trigger(RumorDeflected {
+					position,
+					rumor: *b.0,
+				});
+			}
+		}
+	}
+}
+
+pub fn on_rumors_cancelled(
+	trigger: Trigger<RumorsCancelled>,
+	state: Res<State<GameState>>,
+	mut cmds: Commands,
+) {
+	if *state.get() != GameState::Playing {
+		return;
+	}
+	// the one that was shot down
+	let text = format!(
+		"{}\n{}",
+		trigger.rumors[1].kind().name.to_uppercase(),
+		random_string(&CANCELLED)
+	);
+	cmds.spawn(interaction_text(trigger.position, text));
+}
+
+pub fn on_rumors_merged(
+	trigger: Trigger<RumorsMerged>,
+	state: Res<State<GameState>>,
+	mut cmds: Commands,
+) {
+	if *state.get() != GameState::Playing {
+		return;
+	}
+	let text = format!(
+		"{}\n{}",
+		random_string(&MERGED),
+		trigger.rumor.kind().name.to_uppercase()
+	);
+	cmds.spawn(interaction_text(trigger.position, text));
+}
+
+pub fn on_rumor_deflected(
+	trigger: Trigger<RumorDeflected>,
+	state: Res<State<GameState>>,
+	mut cmds: Commands,
+) {
+	if *state.get() != GameState::Playing {
+		return;
+	}
+	let text = format!(
+		"{}\n{}",
+		trigger.rumor.kind().name.to_uppercase(),
+		random_string(&DEFLECTED)
+	);
+	cmds.spawn(interaction_text(trigger.position, text));
+}
+
+/// Short popup where two rumors met. Only ticked while playing
+fn interaction_text(position: Vec2, text: String) -> impl Bundle {
+	(
+		Text2d::new(text),
+		TextColor(Color::Srgba(Srgba::hex("ffffff").unwrap())),
+		TextEffect::default(),
+		Transform::from_translation(position.extend(900.)),
+		TextLayout::new_with_justify(JustifyText::Center),
+		TextFont {
+			font_size: 20.,
+			..default()
+		},
+		SkipYSort,
+		StateScoped(GameState::Playing),
+	)
+}
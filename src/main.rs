@@ -8,36 +8,57 @@ use bevy_inspector_egui::quick::WorldInspectorPlugin;
 use rand::prelude::*;
 
 mod animations;
+mod antagonists;
+mod archetypes;
 mod assets;
 mod audio;
+mod chains;
 mod config;
+mod contagion;
 mod dialogue;
+mod difficulty;
 mod game_states;
+mod interactions;
 mod menu;
 mod movement;
+mod obstacles;
 mod physics;
+mod pickups;
+mod population;
+mod prediction;
+mod rumors;
 mod shooting;
 mod stonks;
+mod targeting;
 mod traders;
 mod ui;
 
 use animations::*;
+use antagonists::*;
+use archetypes::*;
 use assets::*;
 use audio::*;
+use chains::*;
 use config::*;
+use contagion::*;
 use dialogue::*;
+use difficulty::*;
 use game_states::*;
+use interactions::*;
 use menu::*;
 use movement::*;
+use obstacles::*;
 use physics::*;
+use pickups::*;
+use population::*;
+use prediction::*;
+use rumors::*;
 use shooting::*;
 use stonks::*;
+use targeting::*;
 use traders::*;
 use ui::*;
 
-#[derive(Component)]
-struct Donnie;
-
 #[derive(Component)]
 struct Player;
 
@@ -70,6 +91,8 @@ fn main() {
 		}))
 		.insert_resource(StonksTrading::default())
 		.insert_resource(AssetsBuffer::default())
+		.init_resource::<SpatialHash>()
+		.init_resource::<Contacts>()
 		// Enable this part to use inspector
 		// .add_plugins(EguiPlugin {
 		// 	enable_multipass_for_primary_context: true,
@@ -79,35 +102,89 @@ fn main() {
 		.add_plugins(UIIngamePlugin {})
 		.add_systems(
 			Startup,
-			(window_setup, preload_assets, setup_entities, setup_audio).chain(),
+			(
+				window_setup,
+				preload_assets,
+				setup_entities,
+				setup_obstacles,
+				setup_audio,
+			)
+				.chain(),
 		)
-		.add_systems(OnEnter(GameState::PlaySetup), setup_play)
+		.add_systems(
+			OnEnter(GameState::PlaySetup),
+			(
+				setup_play,
+				clear_pickups,
+				apply_difficulty,
+				sync_antagonists,
+				reset_population,
+			),
+		)
+		.add_systems(OnEnter(GameState::GameOver), clear_pickups)
+		.add_systems(OnEnter(GameState::Playing), setup_effects_ui)
 		.add_systems(
 			// systems that rely on input should be in Update to avoid missing any
 			Update,
-			(player_shooting, player_investing).run_if(in_state(GameState::Playing)),
+			(
+				select_player_rumor.before(player_shooting),
+				player_shooting,
+				player_investing,
+				draw_chain_preview,
+			)
+				.run_if(in_state(GameState::Playing)),
+		)
+		.add_systems(
+			// effects wear off in real time
+			Update,
+			(
+				tick_effects::<MaxTacosBoost>,
+				tick_effects::<TripleShot>,
+				tick_effects::<SlowMotion>,
+				tick_effects::<Silenced>,
+				tick_effects::<HomingShot>,
+				ui_update_effects,
+			)
+				.chain()
+				.run_if(in_state(GameState::Playing)),
 		)
 		.add_systems(
 			// I prefer having most systems in one place to better understand the flow of the game
 			FixedUpdate,
 			(
 				check_game_pause,
-				(check_game_over, charge_player_tacos)
+				(
+					check_game_over,
+					advance_boss_phases,
+					bankruptcies,
+					ipo_waves,
+					charge_player_tacos,
+					spawn_pickups,
+					expire_pickups,
+				)
 					.chain()
 					.run_if(in_state(GameState::Playing)),
 				(
 					handle_timed_shooting,
 					spawn_projectiles,
+					complete_chains,
 					process_text_requests,
 					update_texts,
-					handle_random_movement,
+					(apply_herding, handle_random_movement, handle_flocking).chain(),
+					steer_projectiles,
 					move_entities,
+					expire_projectiles,
 					animations,
 					y_sort,
+					update_spatial_hash,
 					check_collisions,
+					handle_projectile_interactions,
 					handle_collisions,
+					handle_obstacle_hits,
+					collect_pickups,
 					// debug_colliders,
 					tick_trader_timers,
+					spread_sentiment,
 					update_trader_status,
 				)
 					.chain()
@@ -115,7 +192,12 @@ fn main() {
 				(update_stonks_price, ui_update)
 					.chain()
 					.run_if(in_state(GameState::Playing)),
-				(tick_text_effects, ui_update_debug, ui_update_game_stats)
+				(
+					score_chains,
+					tick_text_effects,
+					ui_update_debug,
+					ui_update_game_stats,
+				)
 					.chain()
 					.run_if(in_state(GameState::Playing)),
 				(handle_effect_requests,)
@@ -124,15 +206,38 @@ fn main() {
 			)
 				.chain(),
 		)
-		.add_event::<CollisionEvent>()
+		.add_event::<CollisionStarted>()
+		.add_event::<CollisionEnded>()
 		.add_event::<TraderChange>()
 		.add_event::<SpawnProjectile>()
+		.add_event::<ChainCompleted>()
 		.add_event::<OverheadTextRequest>()
 		.insert_resource(GameStats::default())
 		.insert_resource(AudioLimitCounters([1, 3, 3, 3]))
 		.insert_resource(VolumeSettings::default())
+		.init_resource::<AimAssist>()
+		.init_resource::<Contagion>()
+		.init_resource::<Herding>()
+		.init_resource::<BankShots>()
+		.init_resource::<IpoWaves>()
+		.init_resource::<ProjectilePool>()
+		.init_resource::<InteractionRules>()
+		.init_resource::<ChainTracker>()
+		.init_resource::<Combo>()
+		.init_resource::<PickupSpawner>()
+		.init_resource::<Difficulty>()
+		.init_resource::<DifficultyCurve>()
 		.insert_resource(ClearColor(Color::Srgba(Srgba::hex("6b6a7b").unwrap())))
 		.add_observer(on_stonks_notification)
+		.add_observer(on_max_tacos_boost_removed)
+		.add_observer(on_slow_motion_removed)
+		.add_observer(on_homing_shot_removed)
+		.add_observer(on_rumors_cancelled)
+		.add_observer(on_rumors_merged)
+		.add_observer(on_rumor_deflected)
+		.add_observer(on_rumor_interaction::<RumorsCancelled>)
+		.add_observer(on_rumor_interaction::<RumorsMerged>)
+		.add_observer(on_rumor_interaction::<RumorDeflected>)
 		.add_plugins(ShootingPlugin {})
 		.init_state::<GameState>()
 		.run();
@@ -141,6 +246,7 @@ fn main() {
 fn setup_entities(
 	mut commands: Commands,
 	asset_server: Res<AssetServer>,
+	difficulty: Res<Difficulty>,
 	mut meshes: ResMut<Assets<Mesh>>,
 	mut materials: ResMut<Assets<ColorMaterial>>,
 ) {
@@ -149,84 +255,35 @@ fn setup_entities(
 	// Shadow mesh
 	let mesh_handle = meshes.add(Circle::new(25.));
 	let material_handle = materials.add(Color::hsva(0., 0., 0.2, 0.5));
+	let shadow_assets = ShadowAssets {
+		mesh: mesh_handle.clone(),
+		material: material_handle.clone(),
+	};
+	commands.insert_resource(shadow_assets.clone());
 
 	// Traders
 	for _ in 0..TRADER_COUNT {
-		commands
-			.spawn((
-				Sprite {
-					image: asset_server.load(investor_texture_path()),
-					custom_size: Some(vec2(50., 50.)),
-					image_mode: SpriteImageMode::Scale(ScalingMode::FitCenter),
-					anchor: bevy::sprite::Anchor::BottomCenter,
-					..Default::default()
-				},
-				Transform {
-					translation: Vec3::new(
-						rng.random_range(-WIDTH..WIDTH),
-						rng.random_range(-HEIGHT..HEIGHT),
-						0.,
-					),
-					..Default::default()
-				},
-				Trader::default(),
-				Collider {
-					radius: 25.,
-					offset: Vec2::new(0., 14.),
-				},
-				PhysicsBody {
-					velocity: get_trader_random_velocity(),
-					..Default::default()
-				},
-				RandomMovement::default(),
-				EdgeBehavior::Wraparound,
-				wobble_animation(),
-				// Shadow
-				children![
-					shadow(mesh_handle.clone(), material_handle.clone()),
-					overhead_text(""),
-				],
-			))
-			.observe(audio::on_trader_status_change);
+		let position = Vec2::new(
+			rng.random_range(-WIDTH..WIDTH),
+			rng.random_range(-HEIGHT..HEIGHT),
+		);
+		spawn_trader(&mut commands, &asset_server, &shadow_assets, position);
 	}
 
 	// TODO refactor common stuff?
-	// Donnie
-	commands
-		.spawn((
-			Name::new("Donnie"),
-			Sprite {
-				image: asset_server.load(donnie_texture_path()),
-				custom_size: Some(vec2(70., 70.)),
-				image_mode: SpriteImageMode::Scale(ScalingMode::FitCenter),
-				anchor: bevy::sprite::Anchor::BottomCenter,
-				..Default::default()
-			},
-			Transform {
-				translation: Vec3::new(0., HEIGHT, 0.),
-				..Default::default()
-			},
-			Collider {
-				radius: 25.,
-				offset: Vec2::new(0., 14.),
-			},
-			PhysicsBody {
-				velocity: get_trader_random_velocity(),
-				..Default::default()
-			},
-			RandomMovement::default(),
-			EdgeBehavior::Wraparound,
-			wobble_animation(),
-			Donnie,
-			TimedShooter::default(),
-			// Shadow
-			children![
-				shadow(mesh_handle.clone(), material_handle.clone()),
-				overhead_text("TARIFFS!"),
-			],
-		))
-		.observe(audio::on_donnie_shot)
-		.observe(shooting::on_donnie_shot);
+	// Antagonists
+	let antagonists = difficulty.antagonists();
+	for (i, &kind) in antagonists.iter().enumerate() {
+		let x = (i as f32 + 0.5) / antagonists.len() as f32 * 2. * WIDTH - WIDTH;
+		spawn_antagonist(
+			&mut commands,
+			&asset_server,
+			&shadow_assets,
+			kind,
+			x,
+			*difficulty,
+		);
+	}
 
 	// Taco truck
 	commands
@@ -246,6 +303,11 @@ fn setup_entities(
 			Collider {
 				radius: 25.,
 				offset: Vec2::new(0., 14.),
+				layer: CollisionLayer::PLAYER,
+				mask: CollisionLayer::TRADER
+					| CollisionLayer::DONNIE
+					| CollisionLayer::OBSTACLE
+					| CollisionLayer::PICKUP,
 			},
 			Player,
 			PhysicsBody {
@@ -257,7 +319,10 @@ fn setup_entities(
 			PlayerShootingLogic::default(),
 			wobble_animation(),
 			// Shadow
-			children![shadow(mesh_handle.clone(), material_handle.clone()),],
+			children![
+				shadow(mesh_handle.clone(), material_handle.clone()),
+				overhead_text(""),
+			],
 		))
 		.observe(audio::on_projectile_shot);
 
@@ -289,8 +354,11 @@ fn setup_play(
 	// Reset game stats
 	cmds.insert_resource(StonksTrading::default());
 	cmds.insert_resource(GameStats::default());
+	// before releasing the projectiles, so the old chains are just dropped
+	cmds.insert_resource(ChainTracker::default());
+	cmds.insert_resource(Combo::default());
 	for e in q.iter() {
-		cmds.entity(e).despawn();
+		cmds.entity(e).queue(release_projectile);
 	}
 	spawn_events.clear();
 	next_state.set(GameState::Playing);
@@ -345,77 +413,377 @@ fn window_setup(
 
 fn handle_collisions(
 	mut cmds: Commands,
-	mut collisions: EventReader<CollisionEvent>,
+	mut collisions: EventReader<CollisionStarted>,
 	mut trader_changes: EventWriter<TraderChange>,
 	mut spawn_events: EventWriter<SpawnProjectile>,
-	mut trader: Query<&mut Trader>,
+	mut traders: Query<(
+		Entity,
+		&mut Trader,
+		&Archetype,
+		Option<&mut SplitPattern>,
+		Has<StatusLock>,
+	)>,
 	rumor: Query<&Rumor>,
-	trader_query: Query<(&Transform, Option<&TraderRestTimer>)>,
-	projectile_query: Query<(&Projectile, &Transform)>,
+	trader_query: Query<(&Transform, &Collider)>,
+	projectile_query: Query<
+		(
+			Option<&ShotBy>,
+			&ChainLink,
+			&ShotCharge,
+			&Transform,
+			&PhysicsBody,
+		),
+		With<Projectile>,
+	>,
+	mut chains: ResMut<ChainTracker>,
+	antagonists: Query<(), With<Antagonist>>,
+	stats: Res<GameStats>,
+	curve: Res<DifficultyCurve>,
+	spatial_hash: Res<SpatialHash>,
 ) {
+	let scale = curve.at(&stats);
 	for collision in collisions.read() {
-		// TODO cache component gets?
-		let is_rumor_trader =
-			rumor.get(collision.entity1).is_ok() && trader.get(collision.entity2).is_ok();
-		let is_trader_rumor =
-			trader.get(collision.entity1).is_ok() && rumor.get(collision.entity2).is_ok();
+		let Some((rumor_entity, trader_entity)) =
+			collision.between(CollisionLayer::PROJECTILE, CollisionLayer::TRADER)
+		else {
+			continue;
+		};
 
-		let mut check_rumor_vs_trader = |rumor_entity, trader_entity| {
-			let mut trader = trader.get_mut(trader_entity).unwrap();
-			let rumor = *rumor.get(rumor_entity).unwrap();
-			let (projectile, projectile_transform) = projectile_query.get(rumor_entity).unwrap();
-			if projectile.owner == Some(trader_entity)
-				|| (rumor == Rumor::Taco && trader.status == TraderStatus::Bullish)
-				|| (rumor == Rumor::Tariff && trader.status == TraderStatus::Bearish)
-			{
-				return false;
-			}
-			let (trader_transform, maybe_rest) = trader_query.get(trader_entity).unwrap();
-			if maybe_rest.is_some() {
-				return false;
+		// released (and disabled) by an interaction earlier this tick
+		let (Ok(&rumor), Ok((shot_by, &link, &charge, projectile_transform, projectile_body))) =
+			(rumor.get(rumor_entity), projectile_query.get(rumor_entity))
+		else {
+			continue;
+		};
+		let kind = rumor.kind();
+		let Ok((_, mut trader, archetype, trader_pattern, locked)) = traders.get_mut(trader_entity)
+		else {
+			continue;
+		};
+		if shot_by.is_some_and(|s| s.0 == trader_entity)
+			|| (locked && !kind.market_wide)
+			|| !rumor.affects(trader.status)
+		{
+			continue;
+		}
+		if kind.market_wide {
+			cmds.entity(rumor_entity).queue(release_projectile);
+			for (entity, mut trader, ..) in traders.iter_mut() {
+				if set_by_market_rumor(&mut cmds, &mut trader_changes, entity, &mut trader, rumor) {
+					chains.trader_converted(link);
+				}
 			}
+			continue;
+		}
+		let (trader_transform, trader_collider) = trader_query.get(trader_entity).unwrap();
+		let trader_center = trader_transform.translation.xy() + trader_collider.offset;
+		let archetype = archetype.kind();
+		// split projectiles are shot by traders, so look at who started the chain
+		let difficulty_factor = if chains
+			.origin(link)
+			.is_some_and(|origin| antagonists.contains(origin))
+		{
+			scale.status_duration
+		} else {
+			1.
+		};
+		let status_duration = kind.status_duration * charge.duration_factor() * difficulty_factor;
 
-			// Change trader status
-			let new_status = match rumor {
-				Rumor::Tariff => TraderStatus::Bearish,
-				Rumor::Taco => TraderStatus::Bullish,
-			};
-			let change_event = TraderChange {
-				entity: trader_entity,
-				prev: trader.status,
-				new: new_status,
-			};
-			trader_changes.write(change_event.clone());
-			trader.status = new_status;
-			cmds.entity(trader_entity).insert((
-				TraderStatusTimer(Timer::from_seconds(5., TimerMode::Once)),
-				TraderRestTimer(Timer::from_seconds(0.5, TimerMode::Once)),
-			));
+		if !convert_trader(
+			&mut cmds,
+			&mut trader_changes,
+			trader_entity,
+			&mut trader,
+			archetype,
+			rumor,
+			status_duration,
+		) {
+			cmds.entity(rumor_entity).queue(release_projectile);
+			continue;
+		}
+		chains.trader_converted(link);
 
-			// Spawn chain reaction bullets
-			cmds.entity(rumor_entity).despawn();
-			let position = projectile_transform.translation.xy();
-			let hit_direction = (trader_transform.translation.xy()
-				- projectile_transform.translation.xy())
-			.normalize();
-			let pattern = UniformPattern { bullet_count: 3 };
-			for dir in pattern.direction_iter(hit_direction) {
-				spawn_events.write(SpawnProjectile {
-					projectile_type: rumor,
-					position,
-					direction: dir * PROJECTILE_SPEED,
-					owner: Some(trader_entity),
-				});
+		// Spawn chain reaction bullets
+		cmds.entity(rumor_entity).queue(release_projectile);
+		let position = projectile_transform.translation.xy();
+		let hit_direction = (trader_center - position).normalize();
+		// the rumor has priority over the trader pattern
+		let extra = charge.extra_bullets();
+		let directions = match (kind.split_pattern, trader_pattern) {
+			(Some(mut pattern), _) => pattern.split(hit_direction, projectile_body.velocity, extra),
+			(None, Some(mut pattern)) => {
+				pattern.split(hit_direction, projectile_body.velocity, extra)
+			}
+			(None, None) => {
+				SplitPattern::default().split(hit_direction, projectile_body.velocity, extra)
 			}
-			true
 		};
+		for dir in directions {
+			spawn_events.write(SpawnProjectile {
+				projectile_type: rumor,
+				position,
+				direction: dir * kind.speed,
+				owner: Some(trader_entity),
+				edge_behavior: EdgeBehavior::Destroy,
+				steering: None,
+				chain: Some(link.next()),
+				charge: default(),
+			});
+		}
+
+		// Influencers take the traders around them along
+		if archetype.spread_radius <= 0. {
+			continue;
+		}
+		let neighbours = spatial_hash
+			.query_circle(
+				trader_center,
+				archetype.spread_radius,
+				CollisionLayer::TRADER,
+			)
+			.filter(|neighbour| neighbour.entity != trader_entity)
+			.collect::<Vec<_>>();
+		for neighbour in neighbours {
+			let Ok((_, mut trader, neighbour_archetype, _, locked)) =
+				traders.get_mut(neighbour.entity)
+			else {
+				continue;
+			};
+			if locked || !rumor.affects(trader.status) {
+				continue;
+			}
+			if convert_trader(
+				&mut cmds,
+				&mut trader_changes,
+				neighbour.entity,
+				&mut trader,
+				neighbour_archetype.kind(),
+				rumor,
+				status_duration,
+			) {
+				chains.trader_converted(link);
+			}
+		}
+	}
+}
 
-		if is_rumor_trader {
-			check_rumor_vs_trader(collision.entity1, collision.entity2);
-		} else if is_trader_rumor {
-			check_rumor_vs_trader(collision.entity2, collision.entity1);
+/// Market-wide rumors skip the hit count and break status locks. False if it didn't change anything
+fn set_by_market_rumor(
+	cmds: &mut Commands,
+	trader_changes: &mut EventWriter<TraderChange>,
+	entity: Entity,
+	trader: &mut Trader,
+	rumor: Rumor,
+) -> bool {
+	if !rumor.affects(trader.status) {
+		return false;
+	}
+	let new_status = rumor.resulting_status(trader.status);
+	trader_changes.write(TraderChange {
+		entity,
+		prev: trader.status,
+		new: new_status,
+	});
+	trader.status = new_status;
+	trader.hits = 0;
+	cmds.entity(entity)
+		.remove::<(TraderStatusTimer, StatusLock)>();
+	if new_status != TraderStatus::Neutral {
+		cmds.entity(entity)
+			.insert(TraderStatusTimer(Timer::from_seconds(
+				rumor.kind().status_duration,
+				TimerMode::Once,
+			)));
+	}
+	true
+}
+
+/// Lands one rumor on a trader. False if it's stubborn and needs more hits first
+fn convert_trader(
+	cmds: &mut Commands,
+	trader_changes: &mut EventWriter<TraderChange>,
+	entity: Entity,
+	trader: &mut Trader,
+	archetype: &ArchetypeKind,
+	rumor: Rumor,
+	status_duration: f32,
+) -> bool {
+	trader.hits += 1;
+	if trader.hits < archetype.hits_required {
+		return false;
+	}
+	trader.hits = 0;
+
+	let new_status = rumor.resulting_status(trader.status);
+	trader_changes.write(TraderChange {
+		entity,
+		prev: trader.status,
+		new: new_status,
+	});
+	trader.status = new_status;
+	if new_status == TraderStatus::Neutral {
+		cmds.entity(entity).remove::<TraderStatusTimer>();
+	} else {
+		cmds.entity(entity)
+			.insert(TraderStatusTimer(Timer::from_seconds(
+				status_duration * archetype.status_duration,
+				TimerMode::Once,
+			)));
+		if rumor.kind().locks_status {
+			cmds.entity(entity).insert(StatusLock);
 		}
 	}
+	true
+}
+
+fn spawn_antagonist(
+	commands: &mut Commands,
+	asset_server: &AssetServer,
+	shadow_assets: &ShadowAssets,
+	kind: &'static AntagonistKind,
+	x: f32,
+	difficulty: Difficulty,
+) {
+	let mut antagonist = Antagonist::new(kind);
+	let mut shooter = TimedShooter::default();
+	set_phase(&mut antagonist, &mut shooter, 0, difficulty);
+	commands
+		.spawn((
+			Name::new(kind.name),
+			Sprite {
+				image: asset_server.load((kind.sprite)()),
+				color: kind.tint,
+				custom_size: Some(vec2(70., 70.)),
+				image_mode: SpriteImageMode::Scale(ScalingMode::FitCenter),
+				anchor: bevy::sprite::Anchor::BottomCenter,
+				..Default::default()
+			},
+			Transform {
+				translation: Vec3::new(x, HEIGHT, 0.),
+				..Default::default()
+			},
+			Collider {
+				radius: 25.,
+				offset: Vec2::new(0., 14.),
+				layer: CollisionLayer::DONNIE,
+				mask: CollisionLayer::TRADER | CollisionLayer::PLAYER | CollisionLayer::OBSTACLE,
+			},
+			PhysicsBody {
+				velocity: get_trader_random_velocity(),
+				..Default::default()
+			},
+			RandomMovement::default(),
+			EdgeBehavior::Wraparound,
+			wobble_animation(),
+			antagonist,
+			shooter,
+			// Shadow
+			children![
+				shadow(shadow_assets.mesh.clone(), shadow_assets.material.clone()),
+				overhead_text(kind.phases[0].name),
+			],
+		))
+		.observe(audio::on_antagonist_shot)
+		.observe(shooting::on_antagonist_shot);
+}
+
+/// Respawns the antagonists when the difficulty of the new round wants a different set
+fn sync_antagonists(
+	antagonists: Query<(Entity, &Antagonist)>,
+	difficulty: Res<Difficulty>,
+	asset_server: Res<AssetServer>,
+	shadow_assets: Res<ShadowAssets>,
+	mut contacts: ResMut<Contacts>,
+	mut cmds: Commands,
+) {
+	let wanted = difficulty.antagonists();
+	let mut current = antagonists
+		.iter()
+		.map(|(_, a)| a.kind.name)
+		.collect::<Vec<_>>();
+	current.sort();
+	let mut wanted_names = wanted.iter().map(|kind| kind.name).collect::<Vec<_>>();
+	wanted_names.sort();
+	if current == wanted_names {
+		return;
+	}
+	for (entity, _) in antagonists.iter() {
+		contacts.forget(entity);
+		cmds.entity(entity).despawn();
+	}
+	for (i, &kind) in wanted.iter().enumerate() {
+		let x = (i as f32 + 0.5) / wanted.len() as f32 * 2. * WIDTH - WIDTH;
+		spawn_antagonist(
+			&mut cmds,
+			&asset_server,
+			&shadow_assets,
+			kind,
+			x,
+			*difficulty,
+		);
+	}
+}
+
+/// Shared by every shadow
+#[derive(Resource, Clone)]
+struct ShadowAssets {
+	mesh: Handle<Mesh>,
+	material: Handle<ColorMaterial>,
+}
+
+fn spawn_trader(
+	commands: &mut Commands,
+	asset_server: &AssetServer,
+	shadow_assets: &ShadowAssets,
+	position: Vec2,
+) -> Entity {
+	let archetype = Archetype::random();
+	let kind = archetype.kind();
+	let mut trader = commands.spawn((
+		Name::new(kind.name),
+		Sprite {
+			image: asset_server.load(kind.sprites.path(TraderStatus::Neutral)),
+			color: kind.tint,
+			custom_size: Some(vec2(50., 50.) * kind.size),
+			image_mode: SpriteImageMode::Scale(ScalingMode::FitCenter),
+			anchor: bevy::sprite::Anchor::BottomCenter,
+			..Default::default()
+		},
+		Transform {
+			translation: position.extend(0.),
+			..Default::default()
+		},
+		Trader::default(),
+		SentimentExposure::default(),
+		Insolvency::default(),
+		archetype,
+		Collider {
+			radius: TRADER_RADIUS * kind.size,
+			offset: Vec2::new(0., 14.),
+			layer: CollisionLayer::TRADER,
+			mask: CollisionLayer::PROJECTILE
+				| CollisionLayer::PLAYER
+				| CollisionLayer::DONNIE
+				| CollisionLayer::OBSTACLE,
+		},
+		PhysicsBody {
+			velocity: get_trader_random_velocity() * kind.speed,
+			..Default::default()
+		},
+		EdgeBehavior::Wraparound,
+		kind.split_pattern
+			.unwrap_or_else(|| random_string(&TRADER_SPLIT_PATTERNS)),
+		wobble_animation(),
+		// Shadow
+		children![
+			shadow(shadow_assets.mesh.clone(), shadow_assets.material.clone()),
+			overhead_text(""),
+		],
+	));
+	trader.observe(audio::on_trader_status_change);
+	// switched to `Flocking` by `apply_herding`
+	trader.insert(RandomMovement::default());
+	trader.id()
 }
 
 fn wobble_animation() -> Animation<Transform> {
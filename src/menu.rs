@@ -227,6 +227,67 @@ fn setup_options(mut commands: Commands) {
 					p.spawn(Text::new("Effects"));
 					poor_mans_radio_select(p, AudioType::TraderStatusChange);
 				});
+			parent.spawn(Text::new("Aim assist"));
+			parent
+				.spawn(Node {
+					flex_direction: FlexDirection::Row,
+					..default()
+				})
+				.with_children(|p| {
+					p.spawn(make_small_button("Off"))
+						.observe(set_aim_assist(false));
+					p.spawn(make_small_button("On"))
+						.observe(set_aim_assist(true));
+				});
+			parent.spawn(Text::new("Bank shots off the walls"));
+			parent
+				.spawn(Node {
+					flex_direction: FlexDirection::Row,
+					..default()
+				})
+				.with_children(|p| {
+					p.spawn(make_small_button("Off"))
+						.observe(set_bank_shots(false));
+					p.spawn(make_small_button("On"))
+						.observe(set_bank_shots(true));
+				});
+			parent.spawn(Text::new("Flocking"));
+			parent
+				.spawn(Node {
+					flex_direction: FlexDirection::Row,
+					..default()
+				})
+				.with_children(|p| {
+					p.spawn(make_small_button("Off"))
+						.observe(set_herding(false));
+					p.spawn(make_small_button("On")).observe(set_herding(true));
+				});
+			parent.spawn(Text::new("Herd behaviour"));
+			parent
+				.spawn(Node {
+					flex_direction: FlexDirection::Row,
+					..default()
+				})
+				.with_children(|p| {
+					p.spawn(make_small_button("Off"))
+						.observe(set_contagion(false));
+					p.spawn(make_small_button("On"))
+						.observe(set_contagion(true));
+				});
+			parent.spawn(Text::new("Difficulty"));
+			parent
+				.spawn(Node {
+					flex_direction: FlexDirection::Row,
+					..default()
+				})
+				.with_children(|p| {
+					p.spawn(make_small_button("Easy"))
+						.observe(set_difficulty(Difficulty::Easy));
+					p.spawn(make_small_button("Normal"))
+						.observe(set_difficulty(Difficulty::Normal));
+					p.spawn(make_small_button("Hard"))
+						.observe(set_difficulty(Difficulty::Hard));
+				});
 			parent
 				.spawn(make_button("Back"))
 				.observe(change_state(GameState::Menu));
@@ -2,10 +2,101 @@ use std::ops::DerefMut;
 
 use crate::*;
 
-#[derive(Component)]
+#[derive(Component, Clone, Copy)]
 pub enum EdgeBehavior {
 	Wraparound,
+	/// Releases the projectile to the pool when leaving the arena
 	Destroy,
+	/// Reflects velocity off the arena border
+	Bounce,
+}
+
+/// Changes the direction of a projectile every tick. Speed stays the same
+#[derive(Component, Clone, Copy)]
+pub enum ProjectileSteering {
+	/// Turns toward the nearest trader the rumor would still change
+	Homing { turn_rate: f32 },
+	/// Sways side to side around the initial direction
+	Weaving {
+		amplitude: f32,
+		frequency: f32,
+		elapsed: f32,
+	},
+	/// Flies straight, then turns back to the owner and gets caught
+	Boomerang {
+		return_after: f32,
+		turn_rate: f32,
+		elapsed: f32,
+	},
+}
+
+impl ProjectileSteering {
+	pub fn homing() -> Self {
+		Self::Homing {
+			turn_rate: HOMING_TURN_RATE,
+		}
+	}
+
+	pub fn weaving() -> Self {
+		Self::Weaving {
+			amplitude: WEAVE_AMPLITUDE,
+			frequency: WEAVE_FREQUENCY,
+			elapsed: 0.,
+		}
+	}
+
+	pub fn boomerang() -> Self {
+		Self::Boomerang {
+			return_after: BOOMERANG_RETURN_TIME,
+			turn_rate: HOMING_TURN_RATE * 2.,
+			elapsed: 0.,
+		}
+	}
+
+	/// Velocity after `dt`. `target` is the trader to home in on, `owner` where a boomerang returns to.
+	/// Doesn't touch the world, so the chain preview can run it too
+	pub fn steer(
+		&mut self,
+		velocity: Vec2,
+		position: Vec2,
+		target: Option<Vec2>,
+		owner: Option<Vec2>,
+		dt: f32,
+	) -> Vec2 {
+		match self {
+			ProjectileSteering::Homing { turn_rate } => target.map_or(velocity, |target| {
+				turn_towards(velocity, target - position, *turn_rate * dt)
+			}),
+			ProjectileSteering::Weaving {
+				amplitude,
+				frequency,
+				elapsed,
+			} => {
+				let sway = |t: f32| *amplitude * (t * *frequency * std::f32::consts::TAU).sin();
+				let angle = sway(*elapsed + dt) - sway(*elapsed);
+				*elapsed += dt;
+				Vec2::from_angle(angle).rotate(velocity)
+			}
+			ProjectileSteering::Boomerang {
+				return_after,
+				turn_rate,
+				elapsed,
+			} => {
+				*elapsed += dt;
+				match owner {
+					Some(owner) if *elapsed >= *return_after => {
+						turn_towards(velocity, owner - position, *turn_rate * dt)
+					}
+					_ => velocity,
+				}
+			}
+		}
+	}
+
+	/// A boomerang on its way back
+	pub fn returning(&self) -> bool {
+		matches!(self, ProjectileSteering::Boomerang { return_after, elapsed, .. } if elapsed >= return_after)
+	}
 }
 
 #[derive(Component, Clone)]
@@ -14,6 +105,70 @@ pub enum RandomMovement {
 	Idle(Timer),
 }
 
+/// Boids-style herd movement, used instead of `RandomMovement`.
+/// How much each rule counts depends on the `StatusBehavior`
+#[derive(Component, Clone, Copy)]
+pub struct Flocking {
+	/// Distance at which other traders are seen
+	pub view_radius: f32,
+	/// Units per second
+	pub max_speed: f32,
+}
+
+impl Default for Flocking {
+	fn default() -> Self {
+		Self {
+			view_radius: FLOCK_VIEW_RADIUS,
+			max_speed: FLOCK_MAX_SPEED,
+		}
+	}
+}
+
+/// Traders whose archetype `flocks` move with the herd instead of on their own. Off by default
+#[derive(Resource, Default)]
+pub struct Herding {
+	pub enabled: bool,
+}
+
+pub fn set_herding(enabled: bool) -> impl Fn(Trigger<Pointer<Click>>, ResMut<Herding>) {
+	move |_: Trigger<Pointer<Click>>, mut herding: ResMut<Herding>| {
+		herding.enabled = enabled;
+	}
+}
+
+/// Swaps `RandomMovement` and `Flocking` to match `Herding`, also for traders spawned later
+pub fn apply_herding(
+	herding: Res<Herding>,
+	traders: Query<(Entity, &Archetype, Has<Flocking>), With<Trader>>,
+	mut cmds: Commands,
+) {
+	for (entity, archetype, flocking) in traders.iter() {
+		let flocks = herding.enabled && archetype.kind().flocks;
+		if flocks == flocking {
+			continue;
+		}
+		if flocks {
+			cmds.entity(entity)
+				.remove::<RandomMovement>()
+				.insert(Flocking::default());
+		} else {
+			cmds.entity(entity)
+				.remove::<Flocking>()
+				.insert(RandomMovement::default());
+		}
+	}
+}
+
+/// Weights of the boid rules. Negative cohesion pushes away from the herd
+#[derive(Clone, Copy)]
+pub struct FlockWeights {
+	pub separation: f32,
+	pub alignment: f32,
+	pub cohesion: f32,
+	/// Random nudge so lone traders keep moving
+	pub wander: f32,
+}
+
 #[derive(Component)]
 pub struct SkipYSort;
 
@@ -41,18 +196,18 @@ pub fn move_entities(
 		Option<&EdgeBehavior>,
 		Option<&mut Sprite>,
 	)>,
+	time: Res<Time>,
 	mut cmds: Commands,
 ) {
-	for (entity, body, mut transform, maybe_edge, maybe_sprite) in query.iter_mut() {
-		// transform.translation = ((transform.translation.xy() + body.velocity), 0.).into()
-		transform.translation.x += body.velocity.x;
-		transform.translation.y += body.velocity.y;
+	for (entity, mut body, mut transform, maybe_edge, maybe_sprite) in query.iter_mut() {
+		transform.translation += body.integrate(time.delta_secs()).extend(0.);
 		if let Some(mut s) = maybe_sprite {
 			s.flip_x = body.velocity.x < 0.
 		}
 
 		match maybe_edge {
 			Some(EdgeBehavior::Wraparound) => {
+				let before = transform.translation;
 				if transform.translation.x > WIDTH {
 					transform.translation.x -= WIDTH * 2.
 				}
@@ -65,19 +220,41 @@ pub fn move_entities(
 				if transform.translation.y < -HEIGHT {
 					transform.translation.y += HEIGHT * 2.
 				}
+				// don't sweep across the whole field
+				if transform.translation != before {
+					body.last_motion = Vec2::ZERO;
+				}
 			}
 			Some(EdgeBehavior::Destroy) => {
 				if transform.translation.x > WIDTH {
-					cmds.entity(entity).despawn();
+					cmds.entity(entity).queue(release_projectile);
 				}
 				if transform.translation.x < -WIDTH {
-					cmds.entity(entity).despawn();
+					cmds.entity(entity).queue(release_projectile);
 				}
 				if transform.translation.y > HEIGHT {
-					cmds.entity(entity).despawn();
+					cmds.entity(entity).queue(release_projectile);
 				}
 				if transform.translation.y < -HEIGHT {
-					cmds.entity(entity).despawn();
+					cmds.entity(entity).queue(release_projectile);
+				}
+			}
+			Some(EdgeBehavior::Bounce) => {
+				if transform.translation.x > WIDTH {
+					transform.translation.x = WIDTH;
+					body.velocity.x = -body.velocity.x.abs();
+				}
+				if transform.translation.x < -WIDTH {
+					transform.translation.x = -WIDTH;
+					body.velocity.x = body.velocity.x.abs();
+				}
+				if transform.translation.y > HEIGHT {
+					transform.translation.y = HEIGHT;
+					body.velocity.y = -body.velocity.y.abs();
+				}
+				if transform.translation.y < -HEIGHT {
+					transform.translation.y = -HEIGHT;
+					body.velocity.y = body.velocity.y.abs();
 				}
 			}
 			None => (),
@@ -85,6 +262,66 @@ pub fn move_entities(
 	}
 }
 
+pub fn steer_projectiles(
+	mut projectiles: Query<(
+		Entity,
+		&mut ProjectileSteering,
+		&mut PhysicsBody,
+		&Transform,
+		Option<&ShotBy>,
+		&Rumor,
+	)>,
+	traders: Query<(Entity, &Transform, &Collider, &Trader)>,
+	positions: Query<&Transform, Without<Projectile>>,
+	time: Res<Time>,
+	mut cmds: Commands,
+) {
+	for (entity, mut steering, mut body, transform, shot_by, rumor) in projectiles.iter_mut() {
+		let owner = shot_by.map(|s| s.0);
+		let position = transform.translation.xy();
+		let target = match *steering {
+			ProjectileSteering::Homing { .. } => nearest_target(
+				position,
+				traders
+					.iter()
+					.filter(|(e, _, _, t)| owner != Some(*e) && rumor.affects(t.status))
+					.map(|(_, t, collider, _)| t.translation.xy() + collider.offset),
+			),
+			_ => None,
+		};
+		let owner_position = owner
+			.and_then(|e| positions.get(e).ok())
+			.map(|t| t.translation.xy());
+		body.velocity = steering.steer(
+			body.velocity,
+			position,
+			target,
+			owner_position,
+			time.delta_secs(),
+		);
+		// caught by its owner
+		if steering.returning()
+			&& owner_position.is_some_and(|o| o.distance(position) < PROJECTILE_RADIUS * 2.)
+		{
+			cmds.entity(entity).queue(release_projectile);
+		}
+	}
+}
+
+/// Closest of `candidates` to `position`, for homing projectiles
+pub fn nearest_target(position: Vec2, candidates: impl Iterator<Item = Vec2>) -> Option<Vec2> {
+	candidates.min_by(|a, b| {
+		a.distance_squared(position)
+			.total_cmp(&b.distance_squared(position))
+	})
+}
+
+/// Rotates `velocity` toward `direction` by at most `max_angle` radians
+fn turn_towards(velocity: Vec2, direction: Vec2, max_angle: f32) -> Vec2 {
+	let angle = velocity.angle_to(direction).clamp(-max_angle, max_angle);
+	Vec2::from_angle(angle).rotate(velocity)
+}
+
 pub fn animations(
 	mut animations: Query<(&mut Transform, &mut Animation<Transform>)>,
 	time: Res<Time>,
@@ -101,13 +338,54 @@ pub fn y_sort(mut q: Query<&mut Transform, (With<Sprite>, Without<SkipYSort>)>)
 }
 
 pub fn handle_random_movement(
-	mut q: Query<(&mut PhysicsBody, &mut RandomMovement)>,
+	mut q: Query<(
+		&mut PhysicsBody,
+		&mut RandomMovement,
+		&Transform,
+		Option<&Trader>,
+		Option<&Archetype>,
+	)>,
+	player: Single<&Transform, With<Player>>,
+	mut trader_changes: EventReader<TraderChange>,
 	time: Res<Time>,
 ) {
-	for (mut body, mut random_movement) in q.iter_mut() {
+	let player = player.translation.xy();
+	let new_velocity =
+		|transform: &Transform, trader: Option<&Trader>, archetype: Option<&Archetype>| {
+			let wander = get_trader_random_velocity() * archetype.map_or(1., |a| a.kind().speed);
+			match trader {
+				Some(trader) => {
+					trader
+						.status
+						.behavior()
+						.steer(wander, transform.translation.xy(), player)
+				}
+				None => wander,
+			}
+		};
+	// react right away instead of finishing the current move
+	for change in trader_changes.read() {
+		let Ok((mut body, mut random_movement, transform, trader, archetype)) =
+			q.get_mut(change.entity)
+		else {
+			continue;
+		};
+		*random_movement =
+			RandomMovement::Moving(Timer::from_seconds(MOVEMENT_TIME, TimerMode::Once));
+		body.velocity = new_velocity(transform, trader, archetype);
+	}
+
+	for (mut body, mut random_movement, transform, trader, archetype) in q.iter_mut() {
 		match random_movement.deref_mut() {
 			RandomMovement::Moving(timer) => {
-				if timer.tick(time.delta()).just_finished() {
+				let arrived = trader.is_some_and(|t| {
+					t.status.behavior().overshooting(
+						transform.translation.xy(),
+						body.velocity,
+						player,
+					)
+				});
+				if timer.tick(time.delta()).just_finished() || arrived {
 					*random_movement =
 						RandomMovement::Idle(Timer::from_seconds(IDLE_TIME, TimerMode::Once));
 					body.velocity = Vec2::ZERO;
@@ -117,9 +395,74 @@ pub fn handle_random_movement(
 				if timer.tick(time.delta()).just_finished() {
 					*random_movement =
 						RandomMovement::Moving(Timer::from_seconds(MOVEMENT_TIME, TimerMode::Once));
-					body.velocity = get_trader_random_velocity();
+					body.velocity = new_velocity(transform, trader, archetype);
 				}
 			}
 		};
 	}
 }
+
+pub fn handle_flocking(
+	mut q: Query<(
+		Entity,
+		&Transform,
+		&mut PhysicsBody,
+		&Flocking,
+		&Trader,
+		Option<&Archetype>,
+	)>,
+	player: Single<&Transform, With<Player>>,
+	spatial_hash: Res<SpatialHash>,
+	time: Res<Time>,
+) {
+	let player = player.translation.xy();
+	// steer everyone from the same snapshot of the herd
+	let steering = q
+		.iter()
+		.map(|(entity, transform, body, flocking, trader, _)| {
+			let position = transform.translation.xy();
+			let neighbours = spatial_hash
+				.query_circle(position, flocking.view_radius, CollisionLayer::TRADER)
+				.filter(|n| n.entity != entity)
+				.filter_map(|n| q.get(n.entity).ok())
+				.map(|(_, t, b, ..)| (t.translation.xy(), b.velocity))
+				.collect::<Vec<_>>();
+
+			let behavior = trader.status.behavior();
+			let weights = behavior.flock;
+			let wander = Vec2::from_angle(rand::random_range(0.0..std::f32::consts::TAU));
+			let mut steer = wander * weights.wander
+				+ behavior.goal_direction(position, player) * behavior.goal_weight;
+			if !neighbours.is_empty() {
+				let count = neighbours.len() as f32;
+				let separation = neighbours
+					.iter()
+					.map(|(other, _)| {
+						let offset = position - *other;
+						offset.normalize_or_zero() * (1. - offset.length() / flocking.view_radius)
+					})
+					.sum::<Vec2>();
+				let average_velocity = neighbours.iter().map(|(_, v)| *v).sum::<Vec2>() / count;
+				let alignment = (average_velocity - body.velocity) / flocking.max_speed;
+				let center = neighbours.iter().map(|(p, _)| *p).sum::<Vec2>() / count;
+				let cohesion = (center - position) / flocking.view_radius;
+				steer += separation * weights.separation
+					+ alignment * weights.alignment
+					+ cohesion * weights.cohesion;
+			}
+			(entity, steer * flocking.max_speed)
+		})
+		.collect::<Vec<_>>();
+
+	for (entity, steer) in steering {
+		let (_, transform, mut body, flocking, trader, archetype) = q.get_mut(entity).unwrap();
+		let behavior = trader.status.behavior();
+		let max_speed =
+			flocking.max_speed * behavior.speed * archetype.map_or(1., |a| a.kind().speed);
+		// never stop completely, the herd would freeze
+		let velocity =
+			(body.velocity + steer * time.delta_secs()).clamp_length(max_speed * 0.3, max_speed);
+		// but don't get pushed across the border or into the truck by that minimum speed
+		body.velocity = behavior.hold_back(transform.translation.xy(), velocity, player);
+	}
+}
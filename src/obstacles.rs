@@ -0,0 +1,128 @@
+use crate::*;
+
+/// Market stalls and other static stuff in the arena. Blocks traders and stops or reflects projectiles
+#[derive(Component)]
+#[require(StaticBody)]
+pub struct Obstacle {
+	pub projectile_response: ProjectileResponse,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum ProjectileResponse {
+	Reflect,
+	Absorb,
+}
+
+/// Player tacos bounce off the arena border instead of leaving it
+#[derive(Resource, Default)]
+pub struct BankShots {
+	pub enabled: bool,
+}
+
+impl BankShots {
+	pub fn edge_behavior(&self) -> EdgeBehavior {
+		if self.enabled {
+			EdgeBehavior::Bounce
+		} else {
+			EdgeBehavior::Destroy
+		}
+	}
+}
+
+pub fn set_bank_shots(enabled: bool) -> impl Fn(Trigger<Pointer<Click>>, ResMut<BankShots>) {
+	move |_: Trigger<Pointer<Click>>, mut bank_shots: ResMut<BankShots>| {
+		bank_shots.enabled = enabled;
+	}
+}
+
+/// Positions of the taco stands in the arena
+const MARKET_STALLS: [(Vec2, ProjectileResponse); 4] = [
+	(
+		Vec2::new(-WIDTH / 2., HEIGHT / 2.),
+		ProjectileResponse::Reflect,
+	),
+	(
+		Vec2::new(WIDTH / 2., HEIGHT / 2.),
+		ProjectileResponse::Absorb,
+	),
+	(
+		Vec2::new(-WIDTH / 2., -HEIGHT / 2.),
+		ProjectileResponse::Absorb,
+	),
+	(
+		Vec2::new(WIDTH / 2., -HEIGHT / 2.),
+		ProjectileResponse::Reflect,
+	),
+];
+
+pub fn setup_obstacles(
+	mut commands: Commands,
+	asset_server: Res<AssetServer>,
+	mut meshes: ResMut<Assets<Mesh>>,
+	mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+	let mesh_handle = meshes.add(Circle::new(35.));
+	let material_handle = materials.add(Color::hsva(0., 0., 0.2, 0.5));
+
+	for (position, projectile_response) in MARKET_STALLS {
+		commands.spawn((
+			Name::new("Market stall"),
+			Sprite {
+				image: asset_server.load("taco_man3/taco-stand.png"),
+				custom_size: Some(vec2(80., 80.)),
+				image_mode: SpriteImageMode::Scale(ScalingMode::FitCenter),
+				anchor: bevy::sprite::Anchor::BottomCenter,
+				..Default::default()
+			},
+			Transform::from_translation(position.extend(0.)),
+			Collider {
+				radius: 30.,
+				offset: Vec2::new(0., 20.),
+				layer: CollisionLayer::OBSTACLE,
+				mask: CollisionLayer::TRADER
+					| CollisionLayer::PROJECTILE
+					| CollisionLayer::PLAYER
+					| CollisionLayer::DONNIE,
+			},
+			Obstacle {
+				projectile_response,
+			},
+			children![shadow(mesh_handle.clone(), material_handle.clone())],
+		));
+	}
+}
+
+pub fn handle_obstacle_hits(
+	mut collisions: EventReader<CollisionStarted>,
+	obstacles: Query<(&Obstacle, &Transform, &Collider)>,
+	mut projectiles: Query<(&mut PhysicsBody, &Transform), With<Projectile>>,
+	mut cmds: Commands,
+) {
+	for collision in collisions.read() {
+		let Some((projectile_entity, obstacle_entity)) =
+			collision.between(CollisionLayer::PROJECTILE, CollisionLayer::OBSTACLE)
+		else {
+			continue;
+		};
+		let (Ok((obstacle, obstacle_transform, collider)), Ok((mut body, projectile_transform))) = (
+			obstacles.get(obstacle_entity),
+			projectiles.get_mut(projectile_entity),
+		) else {
+			continue;
+		};
+		match obstacle.projectile_response {
+			ProjectileResponse::Absorb => {
+				cmds.entity(projectile_entity).queue(release_projectile);
+			}
+			ProjectileResponse::Reflect => {
+				let normal = (projectile_transform.translation.xy()
+					- (obstacle_transform.translation.xy() + collider.offset))
+					.normalize_or(-body.velocity.normalize_or_zero());
+				// only reflect when moving towards the obstacle
+				if body.velocity.dot(normal) < 0. {
+					body.velocity = body.velocity.reflect(normal);
+				}
+			}
+		}
+	}
+}
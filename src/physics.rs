@@ -1,61 +1,467 @@
-use bevy::{color::palettes::css::*, prelude::*};
+use bevy::{
+	color::palettes::css::*, ecs::system::SystemParam, platform::collections::HashMap, prelude::*,
+};
 
-#[derive(Component, Default)]
+/// Default size of a spatial hash cell. Grows at runtime if a collider doesn't fit
+pub const SPATIAL_HASH_CELL_SIZE: f32 = 64.;
+
+#[derive(Component)]
 pub struct Collider {
 	pub radius: f32,
 	pub offset: Vec2,
+	/// Layers this collider belongs to
+	pub layer: CollisionLayer,
+	/// Layers this collider can collide with
+	pub mask: CollisionLayer,
+}
+
+impl Default for Collider {
+	fn default() -> Self {
+		Self {
+			radius: 0.,
+			offset: Vec2::ZERO,
+			layer: CollisionLayer::DEFAULT,
+			mask: CollisionLayer::ALL,
+		}
+	}
+}
+
+/// Bit flags used to filter out pairs of colliders before narrow phase
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct CollisionLayer(pub u32);
+
+impl CollisionLayer {
+	pub const DEFAULT: Self = Self(1 << 0);
+	pub const TRADER: Self = Self(1 << 1);
+	pub const PROJECTILE: Self = Self(1 << 2);
+	pub const PLAYER: Self = Self(1 << 3);
+	pub const DONNIE: Self = Self(1 << 4);
+	pub const OBSTACLE: Self = Self(1 << 5);
+	pub const PICKUP: Self = Self(1 << 6);
+	pub const ALL: Self = Self(u32::MAX);
+
+	pub const fn union(self, other: Self) -> Self {
+		Self(self.0 | other.0)
+	}
+
+	pub const fn intersects(self, other: Self) -> bool {
+		self.0 & other.0 != 0
+	}
+}
+
+impl std::ops::BitOr for CollisionLayer {
+	type Output = Self;
+
+	fn bitor(self, rhs: Self) -> Self {
+		self.union(rhs)
+	}
 }
 
-/// Does not displace bodies on collision
-// TODO not used yet
+/// Does not displace bodies on collision.
+/// Overlaps are only reported with [`CollisionStarted`] and [`CollisionEnded`]
 #[derive(Component)]
 #[require(Collider)]
 pub struct AreaTrigger;
 
+/// Integrated with the fixed timestep, so all values are per second
 #[derive(Component, Default)]
 #[require(Transform)]
 pub struct PhysicsBody {
 	pub velocity: Vec2,
-	// pub accel: Vec2,
+	pub accel: Vec2,
+	/// Linear drag. Fraction of velocity lost per second, 0 means no drag
+	pub drag: f32,
+	/// Displacement of the last step, swept by `ContinuousCollision`. Zero after a teleport
+	pub last_motion: Vec2,
+}
+
+impl PhysicsBody {
+	/// Semi-implicit Euler step. Returns the displacement for this step
+	pub fn integrate(&mut self, dt: f32) -> Vec2 {
+		self.velocity += self.accel * dt;
+		// implicit form of the drag so it stays stable with big time steps
+		self.velocity /= 1. + self.drag * dt;
+		self.last_motion = self.velocity * dt;
+		self.last_motion
+	}
+}
+
+/// Never displaced by collisions, the other body takes the whole displacement
+#[derive(Component, Default)]
+#[require(PhysicsBody)]
+pub struct StaticBody;
+
+/// Sweeps the collider along the motion of the last step, so fast bodies can't tunnel through others
+#[derive(Component, Default)]
+#[require(PhysicsBody)]
+pub struct ContinuousCollision;
+
+/// Time of impact, between 0 and 1, of a circle moving from `start` along `motion` against a static circle.
+/// Returns 0 if they are already overlapping at the start
+pub fn sweep_circle(
+	start: Vec2,
+	motion: Vec2,
+	radius: f32,
+	target: Vec2,
+	target_radius: f32,
+) -> Option<f32> {
+	let radii = radius + target_radius;
+	let offset = start - target;
+	let c = offset.length_squared() - radii * radii;
+	if c <= 0. {
+		return Some(0.);
+	}
+	let a = motion.length_squared();
+	let b = 2. * offset.dot(motion);
+	// moving away or not moving at all
+	if a <= f32::EPSILON || b >= 0. {
+		return None;
+	}
+	let discriminant = b * b - 4. * a * c;
+	if discriminant < 0. {
+		return None;
+	}
+	let t = (-b - discriminant.sqrt()) / (2. * a);
+	(t <= 1.).then_some(t)
 }
 
-#[derive(Event)]
-pub struct CollisionEvent {
+#[derive(Clone, Copy)]
+pub struct CollisionPair {
 	pub entity1: Entity,
 	pub entity2: Entity,
+	pub layer1: CollisionLayer,
+	pub layer2: CollisionLayer,
+}
+
+impl CollisionPair {
+	fn key(&self) -> (Entity, Entity) {
+		(
+			self.entity1.min(self.entity2),
+			self.entity1.max(self.entity2),
+		)
+	}
+
+	/// Returns the entities in the order of the requested layers, if the pair is between those layers
+	pub fn between(&self, a: CollisionLayer, b: CollisionLayer) -> Option<(Entity, Entity)> {
+		if self.layer1.intersects(a) && self.layer2.intersects(b) {
+			Some((self.entity1, self.entity2))
+		} else if self.layer2.intersects(a) && self.layer1.intersects(b) {
+			Some((self.entity2, self.entity1))
+		} else {
+			None
+		}
+	}
+}
+
+/// Two colliders started overlapping this tick
+#[derive(Event, Deref)]
+pub struct CollisionStarted(pub CollisionPair);
+
+/// Two colliders stopped overlapping this tick.
+/// One of the entities might have been despawned already
+#[derive(Event, Deref)]
+pub struct CollisionEnded(pub CollisionPair);
+
+/// Pairs of colliders that are currently overlapping, kept across ticks
+#[derive(Resource, Default)]
+pub struct Contacts {
+	current: HashMap<(Entity, Entity), CollisionPair>,
+	previous: HashMap<(Entity, Entity), CollisionPair>,
+}
+
+impl Contacts {
+	/// Drops the contacts of an entity that left the simulation without being despawned,
+	/// so it starts fresh if it comes back. No `CollisionEnded` is sent for them
+	pub fn forget(&mut self, entity: Entity) {
+		self.current
+			.retain(|(a, b), _| *a != entity && *b != entity);
+	}
+}
+
+/// Uniform grid broad phase. Colliders are bucketed by the cell of their center,
+/// so two colliders can only touch if they are in the same or neighbouring cells
+#[derive(Resource)]
+pub struct SpatialHash {
+	cell_size: f32,
+	min_cell_size: f32,
+	cells: HashMap<IVec2, Vec<usize>>,
+	entries: Vec<SpatialEntry>,
+}
+
+#[derive(Clone, Copy)]
+pub struct SpatialEntry {
+	pub entity: Entity,
+	pub center: Vec2,
+	pub radius: f32,
+	pub layer: CollisionLayer,
+	pub mask: CollisionLayer,
+}
+
+impl Default for SpatialHash {
+	fn default() -> Self {
+		Self::new(SPATIAL_HASH_CELL_SIZE)
+	}
+}
+
+impl SpatialHash {
+	pub fn new(cell_size: f32) -> Self {
+		Self {
+			cell_size,
+			min_cell_size: cell_size,
+			cells: default(),
+			entries: default(),
+		}
+	}
+
+	pub fn cell_of(&self, position: Vec2) -> IVec2 {
+		(position / self.cell_size).floor().as_ivec2()
+	}
+
+	/// Replaces the content of the grid.
+	/// Cells must be at least as big as the biggest collider, otherwise neighbouring cells are not enough
+	pub fn rebuild(&mut self, entries: impl IntoIterator<Item = SpatialEntry>) {
+		self.entries.clear();
+		self.entries.extend(entries);
+		let max_diameter = self
+			.entries
+			.iter()
+			.fold(0., |acc: f32, e| acc.max(e.radius * 2.));
+		self.cell_size = self.min_cell_size.max(max_diameter);
+
+		// keep the allocated buckets around, they will most likely be reused next tick
+		for bucket in self.cells.values_mut() {
+			bucket.clear();
+		}
+		for (i, entry) in self.entries.iter().enumerate() {
+			let cell = self.cell_of(entry.center);
+			self.cells.entry(cell).or_default().push(i);
+		}
+	}
+
+	/// Every pair of entries that shares a cell or sits in neighbouring cells and whose layers match.
+	/// Each pair is reported once
+	pub fn candidate_pairs(&self) -> impl Iterator<Item = (SpatialEntry, SpatialEntry)> + '_ {
+		// only half of the neighbours, the other half is covered when visiting from the other cell
+		const HALF_NEIGHBOURHOOD: [IVec2; 4] = [
+			IVec2::new(1, 0),
+			IVec2::new(1, 1),
+			IVec2::new(0, 1),
+			IVec2::new(-1, 1),
+		];
+		self.cells.iter().flat_map(move |(cell, bucket)| {
+			let same_cell = bucket
+				.iter()
+				.enumerate()
+				.flat_map(move |(i, &a)| bucket[i + 1..].iter().map(move |&b| (a, b)));
+			let neighbours = HALF_NEIGHBOURHOOD
+				.iter()
+				.filter_map(move |offset| self.cells.get(&(*cell + *offset)))
+				.flat_map(move |other| {
+					bucket
+						.iter()
+						.flat_map(move |&a| other.iter().map(move |&b| (a, b)))
+				});
+			same_cell
+				.chain(neighbours)
+				.map(|(a, b)| (self.entries[a], self.entries[b]))
+				// both colliders need to accept each other
+				.filter(|(a, b)| a.layer.intersects(b.mask) && b.layer.intersects(a.mask))
+		})
+	}
+
+	/// Entries on one of the `mask` layers that overlap the circle
+	pub fn query_circle(
+		&self,
+		center: Vec2,
+		radius: f32,
+		mask: CollisionLayer,
+	) -> impl Iterator<Item = SpatialEntry> + '_ {
+		// entries are bucketed by their center, which can stick out of the circle by half a cell
+		let reach = Vec2::splat(radius + self.cell_size / 2.);
+		let min = self.cell_of(center - reach);
+		let max = self.cell_of(center + reach);
+		(min.x..=max.x)
+			.flat_map(move |x| (min.y..=max.y).map(move |y| IVec2::new(x, y)))
+			.filter_map(|cell| self.cells.get(&cell))
+			.flatten()
+			.map(|&i| self.entries[i])
+			.filter(move |e| {
+				e.layer.intersects(mask) && e.center.distance(center) < radius + e.radius
+			})
+	}
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct CastHit {
+	pub entity: Entity,
+	/// Point on the surface of the hit collider
+	pub point: Vec2,
+	pub normal: Vec2,
+	/// Distance travelled along the cast before the hit
+	pub distance: f32,
+}
+
+pub struct CastFilter {
+	pub mask: CollisionLayer,
+	pub excluded: Vec<Entity>,
+}
+
+impl Default for CastFilter {
+	fn default() -> Self {
+		Self {
+			mask: CollisionLayer::ALL,
+			excluded: Vec::new(),
+		}
+	}
+}
+
+/// Ray and shape casts against the current colliders
+#[derive(SystemParam)]
+pub struct SpatialQuery<'w, 's> {
+	colliders: Query<'w, 's, (Entity, &'static Collider, &'static Transform)>,
+}
+
+impl SpatialQuery<'_, '_> {
+	/// First collider touched by a circle moving from `origin` along `direction`.
+	/// A radius of 0 makes it a ray cast
+	pub fn cast_circle(
+		&self,
+		origin: Vec2,
+		radius: f32,
+		direction: Dir2,
+		max_distance: f32,
+		filter: &CastFilter,
+	) -> Option<CastHit> {
+		let motion = direction * max_distance;
+		self.colliders
+			.iter()
+			.filter(|(entity, collider, _)| {
+				collider.layer.intersects(filter.mask) && !filter.excluded.contains(entity)
+			})
+			.filter_map(|(entity, collider, transform)| {
+				let center = transform.translation.xy() + collider.offset;
+				let t = sweep_circle(origin, motion, radius, center, collider.radius)?;
+				let distance = t * max_distance;
+				let normal = (origin + direction * distance - center).normalize_or(-*direction);
+				Some(CastHit {
+					entity,
+					point: center + normal * collider.radius,
+					normal,
+					distance,
+				})
+			})
+			.min_by(|a, b| a.distance.total_cmp(&b.distance))
+	}
+}
+
+pub fn update_spatial_hash(
+	mut spatial_hash: ResMut<SpatialHash>,
+	query: Query<(
+		Entity,
+		&Collider,
+		&Transform,
+		Option<(&PhysicsBody, &ContinuousCollision)>,
+	)>,
+) {
+	spatial_hash.rebuild(query.iter().map(|(entity, collider, transform, ccd)| {
+		let center = transform.translation.xy() + collider.offset;
+		// swept bodies cover the whole path of the last step
+		let motion = ccd.map_or(Vec2::ZERO, |(body, _)| body.last_motion);
+		SpatialEntry {
+			entity,
+			center: center - motion * 0.5,
+			radius: collider.radius + motion.length() * 0.5,
+			layer: collider.layer,
+			mask: collider.mask,
+		}
+	}));
 }
 
-// TODO should add spatial hashing broad phase
 pub fn check_collisions(
+	spatial_hash: Res<SpatialHash>,
+	mut contacts: ResMut<Contacts>,
 	mut query: Query<(
 		&mut PhysicsBody,
 		&Collider,
 		&mut Transform,
 		Entity,
 		Option<&AreaTrigger>,
+		Option<&ContinuousCollision>,
+		Has<StaticBody>,
 	)>,
-	mut collisions: EventWriter<CollisionEvent>,
+	mut collisions_started: EventWriter<CollisionStarted>,
+	mut collisions_ended: EventWriter<CollisionEnded>,
 ) {
-	let mut combinations = query.iter_combinations_mut();
-	while let Some([mut e1, mut e2]) = combinations.fetch_next() {
+	let contacts = contacts.as_mut();
+	std::mem::swap(&mut contacts.current, &mut contacts.previous);
+	contacts.current.clear();
+
+	for (a, b) in spatial_hash.candidate_pairs() {
+		// cheap rejection on the positions the grid was built with
+		if a.center.distance(b.center) >= a.radius + b.radius {
+			continue;
+		}
+		let Ok([mut e1, mut e2]) = query.get_many_mut([a.entity, b.entity]) else {
+			continue;
+		};
+		// positions might have changed from previous displacements this tick
 		let axis = (e1.2.translation.xy() + e1.1.offset) - (e2.2.translation.xy() + e2.1.offset);
 		let radii = e1.1.radius + e2.1.radius;
+		let mut time_of_impact = None;
 		if axis.length() >= radii {
-			continue;
+			if e1.5.is_none() && e2.5.is_none() {
+				continue;
+			}
+			// sweep in the reference frame of the second body
+			let motion1 = e1.5.map_or(Vec2::ZERO, |_| e1.0.last_motion);
+			let motion2 = e2.5.map_or(Vec2::ZERO, |_| e2.0.last_motion);
+			let relative_motion = motion1 - motion2;
+			let Some(t) = sweep_circle(
+				axis - relative_motion,
+				relative_motion,
+				e1.1.radius,
+				Vec2::ZERO,
+				e2.1.radius,
+			) else {
+				continue;
+			};
+			time_of_impact = Some((t, motion1, motion2));
 		}
 		// handle collision
-		collisions.write(CollisionEvent {
+		let pair = CollisionPair {
 			entity1: e1.3,
 			entity2: e2.3,
-		});
-		if e1.4.is_some() || e2.4.is_some() {
-			// one is trigger, avoid displacement
+			layer1: e1.1.layer,
+			layer2: e2.1.layer,
+		};
+		if contacts.previous.remove(&pair.key()).is_none() {
+			collisions_started.write(CollisionStarted(pair));
+		}
+		contacts.current.insert(pair.key(), pair);
+		if e1.4.is_some() || e2.4.is_some() || (e1.6 && e2.6) {
+			// one is trigger or both are static, avoid displacement
+			continue;
+		}
+		if let Some((t, motion1, motion2)) = time_of_impact {
+			// went through each other during the step, move back to the point of impact
+			e1.2.translation -= (motion1 * (1. - t)).extend(0.);
+			e2.2.translation -= (motion2 * (1. - t)).extend(0.);
 			continue;
 		}
 		// simple displacement to resolve collision
-		let penetration = (radii - axis.length()) * axis.normalize();
-		e1.2.translation += (penetration * 0.5).extend(0.);
-		e2.2.translation -= (penetration * 0.5).extend(0.);
+		let penetration = (radii - axis.length()) * axis.normalize_or(Vec2::X);
+		let share1 = match (e1.6, e2.6) {
+			(true, _) => 0.,
+			(_, true) => 1.,
+			_ => 0.5,
+		};
+		e1.2.translation += (penetration * share1).extend(0.);
+		e2.2.translation -= (penetration * (1. - share1)).extend(0.);
+	}
+
+	// whatever is left from last tick is not touching anymore
+	for (_, pair) in contacts.previous.drain() {
+		collisions_ended.write(CollisionEnded(pair));
 	}
 }
 
@@ -0,0 +1,340 @@
+use crate::*;
+use std::ops::DerefMut;
+
+use bevy::ecs::component::Mutable;
+
+/// Collected by driving the taco truck over it
+#[derive(Component)]
+#[require(PhysicsBody)]
+pub struct Pickup {
+	pub kind: PickupKind,
+	/// Disappears when it runs out
+	pub timer: Timer,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum PickupKind {
+	Refill,
+	MaxTacos,
+	TripleShot,
+	SlowMotion,
+	DonnieSilence,
+	HomingShot,
+}
+
+const PICKUP_KINDS: [PickupKind; 6] = [
+	PickupKind::Refill,
+	PickupKind::MaxTacos,
+	PickupKind::TripleShot,
+	PickupKind::SlowMotion,
+	PickupKind::DonnieSilence,
+	PickupKind::HomingShot,
+];
+
+impl PickupKind {
+	pub fn label(self) -> &'static str {
+		match self {
+			PickupKind::Refill => "REFILL",
+			PickupKind::MaxTacos => "MORE TACOS",
+			PickupKind::TripleShot => "TRIPLE SHOT",
+			PickupKind::SlowMotion => "SLOW-MO",
+			PickupKind::DonnieSilence => "DONNIE MUTED",
+			PickupKind::HomingShot => "HOMING TACOS",
+		}
+	}
+
+	fn color(self) -> Color {
+		use bevy::color::palettes::css::*;
+		match self {
+			PickupKind::Refill => WHITE,
+			PickupKind::MaxTacos => GOLD,
+			PickupKind::TripleShot => ORANGE_RED,
+			PickupKind::SlowMotion => DEEP_SKY_BLUE,
+			PickupKind::DonnieSilence => VIOLET,
+			PickupKind::HomingShot => LIME,
+		}
+		.into()
+	}
+}
+
+#[derive(Resource)]
+pub struct PickupSpawner(pub Timer);
+
+impl Default for PickupSpawner {
+	fn default() -> Self {
+		Self(Timer::from_seconds(PICKUP_SPAWN_TIME, TimerMode::Repeating))
+	}
+}
+
+/// Effect of a pickup that wears off. Ticked in real time, so slow-motion doesn't make it last longer
+pub trait TimedEffect: Component<Mutability = Mutable> + DerefMut<Target = Timer> {
+	const LABEL: &'static str;
+}
+
+/// On the player. `PICKUP_MAX_TACOS_BOOST` more tacos can be stored
+#[derive(Component, Deref, DerefMut)]
+pub struct MaxTacosBoost(pub Timer);
+
+impl TimedEffect for MaxTacosBoost {
+	const LABEL: &'static str = "MORE TACOS";
+}
+
+/// On the player. Every shot fires three rumors in a cone
+#[derive(Component, Deref, DerefMut)]
+pub struct TripleShot(pub Timer);
+
+impl TimedEffect for TripleShot {
+	const LABEL: &'static str = "TRIPLE SHOT";
+}
+
+/// On the player. Virtual time runs at `SLOW_MOTION_SPEED`
+#[derive(Component, Deref, DerefMut)]
+pub struct SlowMotion(pub Timer);
+
+impl TimedEffect for SlowMotion {
+	const LABEL: &'static str = "SLOW-MO";
+}
+
+/// On every antagonist. Don't shoot until it wears off
+#[derive(Component, Deref, DerefMut)]
+pub struct Silenced(pub Timer);
+
+impl TimedEffect for Silenced {
+	const LABEL: &'static str = "DONNIE MUTED";
+}
+
+/// On the player. Tacos home in on the traders
+#[derive(Component, Deref, DerefMut)]
+pub struct HomingShot(pub Timer);
+
+impl TimedEffect for HomingShot {
+	const LABEL: &'static str = "HOMING TACOS";
+}
+
+fn effect_timer() -> Timer {
+	Timer::from_seconds(PICKUP_EFFECT_TIME, TimerMode::Once)
+}
+
+#[derive(Component)]
+pub struct EffectsText;
+
+pub fn spawn_pickups(
+	mut spawner: ResMut<PickupSpawner>,
+	pickups: Query<(), With<Pickup>>,
+	time: Res<Time>,
+	asset_server: Res<AssetServer>,
+	mut cmds: Commands,
+) {
+	if !spawner.0.tick(time.delta()).just_finished() || pickups.iter().len() >= PICKUP_MAX_ACTIVE {
+		return;
+	}
+	let kind = random_string(&PICKUP_KINDS);
+	// away from the borders so it can be reached
+	let position = Vec2::new(
+		rand::random_range(-WIDTH * 0.8..WIDTH * 0.8),
+		rand::random_range(-HEIGHT * 0.8..HEIGHT * 0.8),
+	);
+	cmds.spawn((
+		Name::new("Pickup"),
+		Sprite {
+			image: asset_server.load("taco-svgrepo-com.png"),
+			custom_size: Some(vec2(36., 36.)),
+			color: kind.color(),
+			..default()
+		},
+		Transform::from_translation(position.extend(0.)),
+		Collider {
+			radius: 20.,
+			offset: Vec2::ZERO,
+			layer: CollisionLayer::PICKUP,
+			mask: CollisionLayer::PLAYER,
+		},
+		AreaTrigger,
+		Pickup {
+			kind,
+			timer: Timer::from_seconds(PICKUP_LIFETIME, TimerMode::Once),
+		},
+		children![(
+			Text2d::new(kind.label()),
+			TextColor(kind.color()),
+			Transform::from_xyz(0., -28., 10.),
+			TextLayout::new_with_justify(JustifyText::Center),
+			TextFont {
+				font_size: 14.,
+				..default()
+			},
+		)],
+	));
+}
+
+pub fn expire_pickups(
+	mut pickups: Query<(Entity, &mut Pickup)>,
+	time: Res<Time>,
+	mut cmds: Commands,
+) {
+	for (entity, mut pickup) in pickups.iter_mut() {
+		if pickup.timer.tick(time.delta()).just_finished() {
+			cmds.entity(entity).despawn();
+		}
+	}
+}
+
+pub fn collect_pickups(
+	mut collisions: EventReader<CollisionStarted>,
+	pickups: Query<&Pickup>,
+	player: Single<(Entity, &mut PlayerShootingLogic, Has<MaxTacosBoost>), With<Player>>,
+	antagonists: Query<Entity, With<Antagonist>>,
+	mut virtual_time: ResMut<Time<Virtual>>,
+	mut effects: EventWriter<TextEffectRequest>,
+	mut cmds: Commands,
+) {
+	let (player, mut shoot_logic, mut boosted) = player.into_inner();
+	for collision in collisions.read() {
+		let Some((_, pickup_entity)) =
+			collision.between(CollisionLayer::PLAYER, CollisionLayer::PICKUP)
+		else {
+			continue;
+		};
+		let Ok(pickup) = pickups.get(pickup_entity) else {
+			continue;
+		};
+		cmds.entity(pickup_entity).despawn();
+		effects.write(TextEffectRequest {
+			text: pickup.kind.label().to_string(),
+			duration_sec: 1.,
+		});
+		// picking the same effect again restarts its timer
+		match pickup.kind {
+			PickupKind::Refill => shoot_logic.tacos_left = shoot_logic.max_tacos,
+			PickupKind::MaxTacos => {
+				if !boosted {
+					shoot_logic.max_tacos += PICKUP_MAX_TACOS_BOOST;
+					shoot_logic.tacos_left += PICKUP_MAX_TACOS_BOOST;
+					boosted = true;
+				}
+				cmds.entity(player).insert(MaxTacosBoost(effect_timer()));
+			}
+			PickupKind::TripleShot => {
+				cmds.entity(player).insert(TripleShot(effect_timer()));
+			}
+			PickupKind::SlowMotion => {
+				virtual_time.set_relative_speed(SLOW_MOTION_SPEED);
+				cmds.entity(player).insert(SlowMotion(effect_timer()));
+			}
+			PickupKind::DonnieSilence => {
+				for antagonist in antagonists.iter() {
+					cmds.entity(antagonist).insert(Silenced(effect_timer()));
+				}
+			}
+			PickupKind::HomingShot => {
+				shoot_logic.steering = Some(ProjectileSteering::homing());
+				cmds.entity(player).insert(HomingShot(effect_timer()));
+			}
+		}
+	}
+}
+
+pub fn tick_effects<T: TimedEffect>(
+	mut effects: Query<(Entity, &mut T)>,
+	time: Res<Time<Real>>,
+	mut cmds: Commands,
+) {
+	for (entity, mut effect) in effects.iter_mut() {
+		if effect.tick(time.delta()).just_finished() {
+			cmds.entity(entity).remove::<T>();
+		}
+	}
+}
+
+pub fn on_max_tacos_boost_removed(
+	trigger: Trigger<OnRemove, MaxTacosBoost>,
+	mut shoot_logic: Query<&mut PlayerShootingLogic>,
+) {
+	let Ok(mut shoot_logic) = shoot_logic.get_mut(trigger.target()) else {
+		return;
+	};
+	shoot_logic.max_tacos -= PICKUP_MAX_TACOS_BOOST;
+	shoot_logic.tacos_left = shoot_logic.tacos_left.min(shoot_logic.max_tacos);
+}
+
+pub fn on_homing_shot_removed(
+	trigger: Trigger<OnRemove, HomingShot>,
+	mut shoot_logic: Query<&mut PlayerShootingLogic>,
+) {
+	if let Ok(mut shoot_logic) = shoot_logic.get_mut(trigger.target()) {
+		shoot_logic.steering = None;
+	}
+}
+
+pub fn on_slow_motion_removed(
+	_: Trigger<OnRemove, SlowMotion>,
+	mut virtual_time: ResMut<Time<Virtual>>,
+) {
+	virtual_time.set_relative_speed(1.);
+}
+
+/// Removes pickups and effects left from the last round
+pub fn clear_pickups(
+	pickups: Query<Entity, With<Pickup>>,
+	affected: Query<Entity, Or<(With<Player>, With<Antagonist>)>>,
+	mut cmds: Commands,
+) {
+	for entity in pickups.iter() {
+		cmds.entity(entity).despawn();
+	}
+	for entity in affected.iter() {
+		cmds.entity(entity)
+			.remove::<(MaxTacosBoost, TripleShot, SlowMotion, Silenced, HomingShot)>();
+	}
+}
+
+pub fn setup_effects_ui(mut commands: Commands) {
+	commands.spawn((
+		Name::new("In game UI - effects"),
+		Text::new(""),
+		EffectsText,
+		TextFont {
+			font_size: 30.,
+			..default()
+		},
+		TextShadow::default(),
+		Node {
+			position_type: PositionType::Absolute,
+			bottom: Val::Px(10.),
+			left: Val::Px(10.),
+			..default()
+		},
+		Pickable::IGNORE,
+		GlobalZIndex(2),
+		StateScoped(GameState::Playing),
+	));
+}
+
+pub fn ui_update_effects(
+	mut text: Single<&mut Text, With<EffectsText>>,
+	max_tacos: Query<&MaxTacosBoost>,
+	triple_shot: Query<&TripleShot>,
+	slow_motion: Query<&SlowMotion>,
+	silenced: Query<&Silenced>,
+	homing_shot: Query<&HomingShot>,
+) {
+	fn effect_line<T: TimedEffect>(effects: &Query<&T>) -> Option<String> {
+		// every antagonist is silenced, only show the longest
+		let remaining = effects
+			.iter()
+			.map(|e| e.remaining_secs())
+			.reduce(f32::max)?;
+		Some(format!("{} {}s", T::LABEL, remaining.ceil() as u32))
+	}
+	text.0 = [
+		effect_line(&max_tacos),
+		effect_line(&triple_shot),
+		effect_line(&slow_motion),
+		effect_line(&silenced),
+		effect_line(&homing_shot),
+	]
+	.into_iter()
+	.flatten()
+	.collect::<Vec<_>>()
+	.join("\n");
+}
@@ -0,0 +1,110 @@
+use crate::*;
+
+/// Seconds a trader has been bearish in a row. Goes bankrupt at `BANKRUPTCY_TIME`
+#[derive(Component, Default)]
+pub struct Insolvency(pub f32);
+
+/// Traders join in waves while the price is high
+#[derive(Resource)]
+pub struct IpoWaves {
+	pub cooldown: Timer,
+}
+
+impl Default for IpoWaves {
+	fn default() -> Self {
+		// no wave right at the start of a round
+		Self {
+			cooldown: Timer::from_seconds(IPO_COOLDOWN, TimerMode::Once),
+		}
+	}
+}
+
+pub fn bankruptcies(
+	mut traders: Query<(Entity, &Trader, &mut Insolvency, &Name)>,
+	mut contacts: ResMut<Contacts>,
+	mut effects: EventWriter<TextEffectRequest>,
+	time: Res<Time>,
+	mut cmds: Commands,
+) {
+	let mut count = traders.iter().len();
+	for (entity, trader, mut insolvency, name) in traders.iter_mut() {
+		if trader.status != TraderStatus::Bearish {
+			insolvency.0 = 0.;
+			continue;
+		}
+		insolvency.0 += time.delta_secs();
+		if insolvency.0 < BANKRUPTCY_TIME || count <= MIN_TRADERS {
+			continue;
+		}
+		count -= 1;
+		contacts.forget(entity);
+		cmds.entity(entity).despawn();
+		effects.write(TextEffectRequest {
+			text: format!("{} WENT\nBANKRUPT", name.as_str().to_uppercase()),
+			duration_sec: 1.5,
+		});
+	}
+}
+
+pub fn ipo_waves(
+	mut waves: ResMut<IpoWaves>,
+	stonks: Res<StonksTrading>,
+	traders: Query<(), With<Trader>>,
+	asset_server: Res<AssetServer>,
+	shadow_assets: Res<ShadowAssets>,
+	mut effects: EventWriter<TextEffectRequest>,
+	mut overhead_events: EventWriter<OverheadTextRequest>,
+	time: Res<Time>,
+	mut cmds: Commands,
+) {
+	if !waves.cooldown.tick(time.delta()).finished()
+		|| stonks.price_ratio(stonks.price_current) < IPO_PRICE_RATIO
+	{
+		return;
+	}
+	let room = MAX_TRADERS.saturating_sub(traders.iter().len());
+	if room == 0 {
+		return;
+	}
+	waves.cooldown.reset();
+	effects.write(TextEffectRequest {
+		text: "IPO WAVE!".to_string(),
+		duration_sec: 1.5,
+	});
+	for _ in 0..IPO_WAVE_SIZE.min(room) {
+		// they walk in from the sides
+		let position = Vec2::new(
+			WIDTH * if rand::random_bool(0.5) { -1. } else { 1. },
+			rand::random_range(-HEIGHT..HEIGHT),
+		);
+		let trader = spawn_trader(&mut cmds, &asset_server, &shadow_assets, position);
+		overhead_events.write(OverheadTextRequest {
+			attached_to: trader,
+			text: Some("IPO".to_string()),
+			duration_sec: Some(1.5),
+		});
+	}
+}
+
+/// Brings the market back to `TRADER_COUNT` traders for a new round
+pub fn reset_population(
+	traders: Query<Entity, With<Trader>>,
+	asset_server: Res<AssetServer>,
+	shadow_assets: Res<ShadowAssets>,
+	mut contacts: ResMut<Contacts>,
+	mut cmds: Commands,
+) {
+	cmds.insert_resource(IpoWaves::default());
+	let count = traders.iter().len();
+	for entity in traders.iter().skip(TRADER_COUNT as usize) {
+		contacts.forget(entity);
+		cmds.entity(entity).despawn();
+	}
+	for _ in count..TRADER_COUNT as usize {
+		let position = Vec2::new(
+			rand::random_range(-WIDTH..WIDTH),
+			rand::random_range(-HEIGHT..HEIGHT),
+		);
+		spawn_trader(&mut cmds, &asset_server, &shadow_assets, position);
+	}
+}
@@ -0,0 +1,603 @@
+use crate::*;
+use bevy::platform::collections::HashMap;
+
+/// Shows where a rumor would go and which chain reaction it would start, before shooting
+#[derive(Resource)]
+pub struct AimAssist {
+	pub enabled: bool,
+	/// How many hops of the chain reaction to show
+	pub max_depth: u32,
+	/// How far in the future to simulate
+	pub max_time: f32,
+}
+
+impl Default for AimAssist {
+	fn default() -> Self {
+		Self {
+			enabled: false,
+			max_depth: 3,
+			max_time: 3.,
+		}
+	}
+}
+
+#[derive(Clone, Copy)]
+pub struct SimTrader {
+	pub entity: Entity,
+	pub position: Vec2,
+	pub collider_offset: Vec2,
+	pub radius: f32,
+	pub velocity: Vec2,
+	pub status: TraderStatus,
+	pub split_pattern: SplitPattern,
+	/// See `StatusLock`
+	pub locked: bool,
+	/// Rumors absorbed before the status changes
+	pub hits_left: u32,
+}
+
+#[derive(Clone, Copy)]
+pub struct SimProjectile {
+	/// Assigned by the snapshot when the projectile is spawned
+	pub id: usize,
+	pub rumor: Rumor,
+	pub position: Vec2,
+	pub velocity: Vec2,
+	pub radius: f32,
+	pub owner: Option<Entity>,
+	pub edge_behavior: EdgeBehavior,
+	pub steering: Option<ProjectileSteering>,
+	pub charge: ShotCharge,
+	/// How many hits happened in the chain before this projectile was spawned
+	pub depth: u32,
+}
+
+/// Market stall, never moves
+#[derive(Clone, Copy)]
+pub struct SimObstacle {
+	pub center: Vec2,
+	pub radius: f32,
+	pub response: ProjectileResponse,
+}
+
+/// The path of one projectile of a predicted chain reaction
+pub struct PredictedPath {
+	pub points: Vec<Vec2>,
+	pub depth: u32,
+	/// Trader hit at the end of the path
+	pub hit: Option<Entity>,
+}
+
+/// Copy of the parts of the world that matter for a chain reaction.
+/// Stepping it doesn't touch the ECS world, so it can be run ahead of time
+#[derive(Clone, Default)]
+pub struct WorldSnapshot {
+	pub traders: Vec<SimTrader>,
+	pub obstacles: Vec<SimObstacle>,
+	pub projectiles: Vec<SimProjectile>,
+	next_id: usize,
+}
+
+/// Something that happened to a projectile during a simulation step
+pub enum SimEvent {
+	Spawned(SimProjectile),
+	Bounced(SimProjectile),
+	Hit(SimProjectile, Entity),
+	Removed(SimProjectile),
+}
+
+impl WorldSnapshot {
+	pub fn spawn(&mut self, projectile: SimProjectile) -> SimProjectile {
+		let projectile = SimProjectile {
+			id: self.next_id,
+			..projectile
+		};
+		self.next_id += 1;
+		self.projectiles.push(projectile);
+		projectile
+	}
+
+	/// Same rules as the real systems: steering, edge behaviors, market stalls and the chain reaction split on hit.
+	/// Traders keep their current velocity. Projectiles deeper than `max_depth` are not spawned
+	pub fn step(&mut self, dt: f32, max_depth: u32) -> Vec<SimEvent> {
+		let mut events = Vec::new();
+		for trader in self.traders.iter_mut() {
+			trader.position = wraparound(trader.position + trader.velocity * dt);
+		}
+
+		let mut children = Vec::new();
+		self.projectiles.retain_mut(|projectile| {
+			if let Some(steering) = projectile.steering.as_mut() {
+				let target = nearest_target(
+					projectile.position,
+					self.traders
+						.iter()
+						.filter(|t| {
+							projectile.owner != Some(t.entity) && projectile.rumor.affects(t.status)
+						})
+						.map(|t| t.position + t.collider_offset),
+				);
+				let owner = self
+					.traders
+					.iter()
+					.find(|t| projectile.owner == Some(t.entity))
+					.map(|t| t.position);
+				projectile.velocity =
+					steering.steer(projectile.velocity, projectile.position, target, owner, dt);
+			}
+			projectile.position += projectile.velocity * dt;
+			if out_of_arena(projectile.position) {
+				match projectile.edge_behavior {
+					EdgeBehavior::Bounce => {
+						bounce(&mut projectile.position, &mut projectile.velocity);
+						events.push(SimEvent::Bounced(*projectile));
+					}
+					EdgeBehavior::Wraparound => {
+						projectile.position = wraparound(projectile.position)
+					}
+					EdgeBehavior::Destroy => {
+						events.push(SimEvent::Removed(*projectile));
+						return false;
+					}
+				}
+			}
+
+			if let Some(obstacle) = self
+				.obstacles
+				.iter()
+				.find(|o| o.center.distance(projectile.position) < o.radius + projectile.radius)
+			{
+				match obstacle.response {
+					ProjectileResponse::Absorb => {
+						events.push(SimEvent::Removed(*projectile));
+						return false;
+					}
+					ProjectileResponse::Reflect => {
+						let normal = (projectile.position - obstacle.center)
+							.normalize_or(-projectile.velocity.normalize_or_zero());
+						// only reflect when moving towards the obstacle
+						if projectile.velocity.dot(normal) < 0. {
+							projectile.velocity = projectile.velocity.reflect(normal);
+							events.push(SimEvent::Bounced(*projectile));
+						}
+					}
+				}
+			}
+
+			let kind = projectile.rumor.kind();
+			let Some(trader) = self.traders.iter_mut().find(|t| {
+				projectile.owner != Some(t.entity)
+					&& (!t.locked || kind.market_wide)
+					&& projectile.rumor.affects(t.status)
+					&& (t.position + t.collider_offset).distance(projectile.position)
+						< t.radius + projectile.radius
+			}) else {
+				return true;
+			};
+			events.push(SimEvent::Hit(*projectile, trader.entity));
+			if kind.market_wide {
+				for trader in self.traders.iter_mut() {
+					trader.status = projectile.rumor.resulting_status(trader.status);
+					trader.locked = false;
+				}
+				return false;
+			}
+			if trader.hits_left > 1 {
+				trader.hits_left -= 1;
+				return false;
+			}
+			trader.status = projectile.rumor.resulting_status(trader.status);
+			trader.locked = kind.locks_status && trader.status != TraderStatus::Neutral;
+			if projectile.depth < max_depth {
+				let hit_direction = (trader.position - projectile.position).normalize();
+				let directions = match kind.split_pattern.map(|p| p.deterministic()) {
+					Some(mut pattern) => pattern.split(
+						hit_direction,
+						projectile.velocity,
+						projectile.charge.extra_bullets(),
+					),
+					None => trader.split_pattern.split(
+						hit_direction,
+						projectile.velocity,
+						projectile.charge.extra_bullets(),
+					),
+				};
+				for dir in directions {
+					children.push(SimProjectile {
+						velocity: dir * kind.speed,
+						owner: Some(trader.entity),
+						edge_behavior: EdgeBehavior::Destroy,
+						steering: None,
+						radius: PROJECTILE_RADIUS,
+						charge: default(),
+						depth: projectile.depth + 1,
+						..*projectile
+					});
+				}
+			}
+			false
+		});
+
+		for child in children {
+			let child = self.spawn(child);
+			events.push(SimEvent::Spawned(child));
+		}
+		events
+	}
+}
+
+/// Simulates `shot` on a copy of the snapshot and returns the path of every projectile of the chain
+pub fn predict_chain(
+	snapshot: &WorldSnapshot,
+	shot: SimProjectile,
+	max_depth: u32,
+	max_time: f32,
+	dt: f32,
+) -> Vec<PredictedPath> {
+	let mut world = snapshot.clone();
+	world.projectiles.clear();
+	let mut paths: HashMap<usize, PredictedPath> = HashMap::default();
+	let new_path = |projectile: SimProjectile| PredictedPath {
+		points: vec![projectile.position],
+		depth: projectile.depth,
+		hit: None,
+	};
+	let shot = world.spawn(shot);
+	paths.insert(shot.id, new_path(shot));
+
+	let mut time = 0.;
+	while time < max_time && !world.projectiles.is_empty() {
+		time += dt;
+		for event in world.step(dt, max_depth) {
+			match event {
+				SimEvent::Spawned(projectile) => {
+					paths.insert(projectile.id, new_path(projectile));
+				}
+				SimEvent::Bounced(projectile) | SimEvent::Removed(projectile) => {
+					paths
+						.get_mut(&projectile.id)
+						.unwrap()
+						.points
+						.push(projectile.position);
+				}
+				SimEvent::Hit(projectile, trader) => {
+					let path = paths.get_mut(&projectile.id).unwrap();
+					path.points.push(projectile.position);
+					path.hit = Some(trader);
+				}
+			}
+		}
+	}
+	// projectiles still flying when the simulation ends
+	for projectile in world.projectiles.iter() {
+		paths
+			.get_mut(&projectile.id)
+			.unwrap()
+			.points
+			.push(projectile.position);
+	}
+	paths.into_values().collect()
+}
+
+pub fn draw_chain_preview(
+	aim_assist: Res<AimAssist>,
+	touch_res: Res<Touches>,
+	window: Single<&Window>,
+	camera: Single<(&Camera, &GlobalTransform)>,
+	player: Single<(&Transform, &PlayerShootingLogic, Has<TripleShot>), With<Player>>,
+	traders: Query<(
+		Entity,
+		&Transform,
+		&Collider,
+		&PhysicsBody,
+		&Trader,
+		&Archetype,
+		Option<&SplitPattern>,
+		Has<StatusLock>,
+	)>,
+	obstacles: Query<(&Obstacle, &Transform, &Collider)>,
+	bank_shots: Res<BankShots>,
+	fixed_time: Res<Time<Fixed>>,
+	mut gizmos: Gizmos<DottedGizmoConfig>,
+) {
+	use bevy::color::palettes::css::*;
+
+	if !aim_assist.enabled {
+		return;
+	}
+	let Some(cursor_pos) = aim_position(&touch_res, &window, *camera) else {
+		return;
+	};
+	let (player_transform, shoot_logic, triple_shot) = *player;
+	let start_pos = player_transform.translation.xy();
+	let Ok(dir) = Dir2::new(cursor_pos - start_pos) else {
+		return;
+	};
+
+	let snapshot = WorldSnapshot {
+		traders: traders
+			.iter()
+			.map(
+				|(entity, transform, collider, body, trader, archetype, split_pattern, locked)| {
+					SimTrader {
+						entity,
+						position: transform.translation.xy(),
+						collider_offset: collider.offset,
+						radius: collider.radius,
+						velocity: body.velocity,
+						status: trader.status,
+						split_pattern: split_pattern.copied().unwrap_or_default().deterministic(),
+						locked,
+						hits_left: archetype.kind().hits_required.saturating_sub(trader.hits),
+					}
+				},
+			)
+			.collect(),
+		obstacles: obstacles
+			.iter()
+			.map(|(obstacle, transform, collider)| SimObstacle {
+				center: transform.translation.xy() + collider.offset,
+				radius: collider.radius,
+				response: obstacle.projectile_response,
+			})
+			.collect(),
+		..default()
+	};
+	// same as the rumors shot by the player, if released now
+	let charge = shoot_logic.charge();
+	let paths = shot_angles(triple_shot).iter().flat_map(|angle| {
+		let shot = SimProjectile {
+			id: 0,
+			rumor: shoot_logic.rumor,
+			position: start_pos,
+			velocity: Vec2::from_angle(*angle).rotate(*dir) * shoot_logic.rumor.kind().speed,
+			radius: charge.radius(),
+			owner: None,
+			edge_behavior: bank_shots.edge_behavior(),
+			steering: shoot_logic.steering,
+			charge,
+			depth: 0,
+		};
+		predict_chain(
+			&snapshot,
+			shot,
+			aim_assist.max_depth,
+			aim_assist.max_time,
+			fixed_time.timestep().as_secs_f32(),
+		)
+	});
+
+	for path in paths {
+		// deeper in the chain is less certain
+		let alpha = 0.7 / (path.depth + 1) as f32;
+		gizmos.linestrip_2d(path.points.iter().copied(), ORANGE.with_alpha(alpha));
+		if let Some((_, transform, collider, ..)) = path.hit.and_then(|e| traders.get(e).ok()) {
+			gizmos.circle_2d(
+				Isometry2d::from_translation(transform.translation.xy() + collider.offset),
+				collider.radius,
+				ORANGE.with_alpha(alpha),
+			);
+		}
+	}
+}
+
+pub fn set_aim_assist(enabled: bool) -> impl Fn(Trigger<Pointer<Click>>, ResMut<AimAssist>) {
+	move |_: Trigger<Pointer<Click>>, mut aim_assist: ResMut<AimAssist>| {
+		aim_assist.enabled = enabled;
+	}
+}
+
+fn out_of_arena(position: Vec2) -> bool {
+	position.x.abs() > WIDTH || position.y.abs() > HEIGHT
+}
+
+fn wraparound(mut position: Vec2) -> Vec2 {
+	if position.x.abs() > WIDTH {
+		position.x -= WIDTH * 2. * position.x.signum();
+	}
+	if position.y.abs() > HEIGHT {
+		position.y -= HEIGHT * 2. * position.y.signum();
+	}
+	position
+}
+
+fn bounce(position: &mut Vec2, velocity: &mut Vec2) {
+	if position.x.abs() > WIDTH {
+		position.x = WIDTH * position.x.signum();
+		velocity.x = -velocity.x;
+	}
+	if position.y.abs() > HEIGHT {
+		position.y = HEIGHT * position.y.signum();
+		velocity.y = -velocity.y;
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn trader(id: u32, position: Vec2) -> SimTrader {
+		SimTrader {
+			entity: Entity::from_raw(id),
+			position,
+			collider_offset: Vec2::ZERO,
+			radius: 20.,
+			velocity: Vec2::ZERO,
+			status: TraderStatus::Neutral,
+			split_pattern: SplitPattern::default(),
+			locked: false,
+			hits_left: 1,
+		}
+	}
+
+	fn taco(position: Vec2, velocity: Vec2) -> SimProjectile {
+		SimProjectile {
+			id: 0,
+			rumor: Rumor::Taco,
+			position,
+			velocity,
+			radius: PROJECTILE_RADIUS,
+			owner: None,
+			edge_behavior: EdgeBehavior::Destroy,
+			steering: None,
+			charge: default(),
+			depth: 0,
+		}
+	}
+
+	#[test]
+	fn hit_converts_and_splits() {
+		let mut world = WorldSnapshot {
+			traders: vec![trader(1, Vec2::new(10., 0.))],
+			..default()
+		};
+		world.spawn(taco(Vec2::ZERO, Vec2::X * 100.));
+		let events = world.step(0.01, 3);
+
+		assert_eq!(world.traders[0].status, TraderStatus::Bullish);
+		assert!(matches!(events[0], SimEvent::Hit(_, e) if e == Entity::from_raw(1)));
+		// the default pattern of the trader, owned by it so they don't hit it again
+		assert_eq!(world.projectiles.len(), 3);
+		assert!(
+			world
+				.projectiles
+				.iter()
+				.all(|p| p.depth == 1 && p.owner == Some(Entity::from_raw(1)))
+		);
+	}
+
+	#[test]
+	fn no_split_past_max_depth() {
+		let mut world = WorldSnapshot {
+			traders: vec![trader(1, Vec2::new(10., 0.))],
+			..default()
+		};
+		world.spawn(taco(Vec2::ZERO, Vec2::X * 100.));
+		world.step(0.01, 0);
+
+		assert_eq!(world.traders[0].status, TraderStatus::Bullish);
+		assert!(world.projectiles.is_empty());
+	}
+
+	#[test]
+	fn bounces_off_the_border() {
+		let mut world = WorldSnapshot::default();
+		world.spawn(SimProjectile {
+			edge_behavior: EdgeBehavior::Bounce,
+			..taco(Vec2::new(WIDTH - 1., 0.), Vec2::X * 200.)
+		});
+		let events = world.step(0.1, 3);
+
+		assert!(matches!(events[0], SimEvent::Bounced(_)));
+		let projectile = world.projectiles[0];
+		assert!(projectile.position.x <= WIDTH);
+		assert_eq!(projectile.velocity, Vec2::NEG_X * 200.);
+	}
+
+	#[test]
+	fn destroyed_at_the_border() {
+		let mut world = WorldSnapshot::default();
+		world.spawn(taco(Vec2::new(WIDTH - 1., 0.), Vec2::X * 200.));
+		let events = world.step(0.1, 3);
+
+		assert!(matches!(events[0], SimEvent::Removed(_)));
+		assert!(world.projectiles.is_empty());
+	}
+
+	#[test]
+	fn locked_traders_let_rumors_pass() {
+		let mut world = WorldSnapshot {
+			traders: vec![SimTrader {
+				status: TraderStatus::Bearish,
+				locked: true,
+				..trader(1, Vec2::new(10., 0.))
+			}],
+			..default()
+		};
+		world.spawn(taco(Vec2::ZERO, Vec2::X * 100.));
+		let events = world.step(0.01, 3);
+
+		assert!(events.is_empty());
+		assert_eq!(world.traders[0].status, TraderStatus::Bearish);
+		assert_eq!(world.projectiles.len(), 1);
+	}
+
+	#[test]
+	fn insider_tip_locks_the_trader() {
+		let mut world = WorldSnapshot {
+			traders: vec![trader(1, Vec2::new(10., 0.))],
+			..default()
+		};
+		world.spawn(SimProjectile {
+			rumor: Rumor::InsiderTip,
+			..taco(Vec2::ZERO, Vec2::X * 100.)
+		});
+		world.step(0.01, 0);
+
+		assert_eq!(world.traders[0].status, TraderStatus::Bullish);
+		assert!(world.traders[0].locked);
+	}
+
+	#[test]
+	fn bailout_breaks_locks_everywhere() {
+		let mut world = WorldSnapshot {
+			traders: vec![
+				SimTrader {
+					status: TraderStatus::Bullish,
+					..trader(1, Vec2::new(10., 0.))
+				},
+				SimTrader {
+					status: TraderStatus::Bullish,
+					locked: true,
+					..trader(2, Vec2::new(-300., 100.))
+				},
+			],
+			..default()
+		};
+		world.spawn(SimProjectile {
+			rumor: Rumor::Bailout,
+			..taco(Vec2::ZERO, Vec2::X * 100.)
+		});
+		world.step(0.01, 3);
+
+		assert!(
+			world
+				.traders
+				.iter()
+				.all(|t| t.status == TraderStatus::Neutral && !t.locked)
+		);
+		assert!(world.projectiles.is_empty());
+	}
+
+	#[test]
+	fn stubborn_traders_need_more_hits() {
+		let mut world = WorldSnapshot {
+			traders: vec![SimTrader {
+				hits_left: 2,
+				..trader(1, Vec2::new(10., 0.))
+			}],
+			..default()
+		};
+		world.spawn(taco(Vec2::ZERO, Vec2::X * 100.));
+		world.step(0.01, 3);
+
+		assert_eq!(world.traders[0].status, TraderStatus::Neutral);
+		assert_eq!(world.traders[0].hits_left, 1);
+		assert!(world.projectiles.is_empty());
+	}
+
+	#[test]
+	fn homing_turns_towards_the_trader() {
+		let mut world = WorldSnapshot {
+			traders: vec![trader(1, Vec2::new(0., 200.))],
+			..default()
+		};
+		world.spawn(SimProjectile {
+			steering: Some(ProjectileSteering::homing()),
+			..taco(Vec2::ZERO, Vec2::X * 100.)
+		});
+		world.step(0.1, 3);
+
+		assert!(world.projectiles[0].velocity.y > 0.);
+	}
+}
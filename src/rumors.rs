@@ -0,0 +1,135 @@
+use crate::*;
+
+/// See `Rumor::kind` for what each does
+#[derive(Component, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum Rumor {
+	Tariff,
+	Taco,
+	Bailout,
+	FakeNews,
+	InsiderTip,
+}
+
+pub struct RumorKind {
+	pub name: &'static str,
+	pub sprite: &'static str,
+	pub effect: RumorEffect,
+	/// Overrides the split pattern of the trader that was hit
+	pub split_pattern: Option<SplitPattern>,
+	/// Units per second
+	pub speed: f32,
+	/// Seconds before the trader goes back to neutral
+	pub status_duration: f32,
+	/// Other rumors can't change the trader until it goes back to neutral
+	pub locks_status: bool,
+	/// One hit changes every trader at once, locked ones included, instead of splitting
+	pub market_wide: bool,
+}
+
+#[derive(Clone, Copy)]
+pub enum RumorEffect {
+	Set(TraderStatus),
+	/// Bullish becomes bearish and the other way around. Neutral traders don't care
+	Flip,
+}
+
+const TARIFF: RumorKind = RumorKind {
+	name: "Tariff",
+	sprite: "pile-of-poo-svgrepo-com.png",
+	effect: RumorEffect::Set(TraderStatus::Bearish),
+	// tariffs spread chaotically
+	split_pattern: Some(SplitPattern::Jitter(JitterPattern {
+		bullet_count: 3,
+		jitter: 0.4,
+	})),
+	speed: PROJECTILE_SPEED,
+	status_duration: 5.,
+	locks_status: false,
+	market_wide: false,
+};
+
+const TACO: RumorKind = RumorKind {
+	name: "Taco",
+	sprite: "taco_man3/taco.png",
+	effect: RumorEffect::Set(TraderStatus::Bullish),
+	split_pattern: None,
+	speed: PROJECTILE_SPEED,
+	status_duration: 5.,
+	locks_status: false,
+	market_wide: false,
+};
+
+const BAILOUT: RumorKind = RumorKind {
+	name: "Bailout",
+	sprite: "taco_man3/bailout.PNG",
+	effect: RumorEffect::Set(TraderStatus::Neutral),
+	split_pattern: None,
+	speed: PROJECTILE_SPEED * 0.7,
+	status_duration: 0.,
+	locks_status: false,
+	// calms the whole market
+	market_wide: true,
+};
+
+const FAKE_NEWS: RumorKind = RumorKind {
+	name: "Fake News",
+	sprite: "monkey-svgrepo-com.png",
+	effect: RumorEffect::Flip,
+	split_pattern: None,
+	speed: PROJECTILE_SPEED * 1.3,
+	status_duration: 5.,
+	locks_status: false,
+	market_wide: false,
+};
+
+const INSIDER_TIP: RumorKind = RumorKind {
+	name: "Insider Tip",
+	sprite: "taco-svgrepo-com.png",
+	effect: RumorEffect::Set(TraderStatus::Bullish),
+	// only told to a few
+	split_pattern: Some(SplitPattern::Cone(ConePattern {
+		bullet_count: 2,
+		spread: 0.5,
+	})),
+	speed: PROJECTILE_SPEED,
+	status_duration: 12.,
+	locks_status: true,
+	market_wide: false,
+};
+
+/// Rumors the player can pick from, in the order of the number keys
+pub const PLAYER_RUMORS: [Rumor; 4] = [
+	Rumor::Taco,
+	Rumor::InsiderTip,
+	Rumor::FakeNews,
+	Rumor::Bailout,
+];
+
+impl Rumor {
+	pub fn kind(self) -> &'static RumorKind {
+		match self {
+			Rumor::Tariff => &TARIFF,
+			Rumor::Taco => &TACO,
+			Rumor::Bailout => &BAILOUT,
+			Rumor::FakeNews => &FAKE_NEWS,
+			Rumor::InsiderTip => &INSIDER_TIP,
+		}
+	}
+
+	/// Status of a trader after being hit by this rumor
+	pub fn resulting_status(self, current: TraderStatus) -> TraderStatus {
+		match self.kind().effect {
+			RumorEffect::Set(status) => status,
+			RumorEffect::Flip => match current {
+				TraderStatus::Bullish => TraderStatus::Bearish,
+				TraderStatus::Bearish => TraderStatus::Bullish,
+				TraderStatus::Neutral => TraderStatus::Neutral,
+			},
+		}
+	}
+
+	/// Traders that already believe the rumor let it pass through
+	pub fn affects(self, status: TraderStatus) -> bool {
+		self.resulting_status(status) != status
+	}
+}
@@ -1,37 +1,128 @@
 use crate::*;
-
-#[derive(Component, PartialEq, Clone, Copy)]
-pub enum Rumor {
-	Tariff,
-	Taco,
-}
+use bevy::{ecs::entity_disabling::Disabled, platform::collections::HashMap};
 
 #[derive(Event)]
 pub struct SpawnProjectile {
 	pub projectile_type: Rumor,
 	pub position: Vec2,
 	pub direction: Vec2,
-	pub owner: Option<Entity>, // TODO replace with relationship
+	/// Becomes the `ShotBy` of the projectile
+	pub owner: Option<Entity>,
+	pub edge_behavior: EdgeBehavior,
+	pub steering: Option<ProjectileSteering>,
+	/// None starts a new chain reaction
+	pub chain: Option<ChainLink>,
+	pub charge: ShotCharge,
+}
+
+/// Tacos spent on a shot. Charged shots are bigger, split into more bullets and last longer on traders
+#[derive(Component, Clone, Copy)]
+pub struct ShotCharge(pub u32);
+
+impl Default for ShotCharge {
+	fn default() -> Self {
+		Self(1)
+	}
+}
+
+impl ShotCharge {
+	pub fn radius(self) -> f32 {
+		PROJECTILE_RADIUS * (1. + 0.3 * self.extra_tacos() as f32)
+	}
+
+	pub fn extra_bullets(self) -> u32 {
+		self.extra_tacos()
+	}
+
+	/// Multiplier of the status duration of the rumor
+	pub fn duration_factor(self) -> f32 {
+		1. + 0.5 * self.extra_tacos() as f32
+	}
+
+	fn extra_tacos(self) -> u32 {
+		self.0.saturating_sub(1)
+	}
 }
 
 #[derive(Component)]
 pub struct TimedShooter {
-	shooting_timer: Timer,
-	shot_type: Rumor,
+	pub shooting_timer: Timer,
+	/// One is picked at random for every shot
+	pub shot_types: Vec<Rumor>,
+	/// One is picked at random for every shot. Empty shoots straight
+	pub steering: Vec<ProjectileSteering>,
+	/// One is picked at random for every shot
+	pub targeting: Vec<TargetingStrategy>,
 }
 
 impl Default for TimedShooter {
 	fn default() -> Self {
 		Self {
 			shooting_timer: Timer::from_seconds(2.0, TimerMode::Repeating),
-			shot_type: Rumor::Tariff,
+			shot_types: vec![Rumor::Tariff],
+			steering: Vec::new(),
+			targeting: vec![TargetingStrategy::Random],
 		}
 	}
 }
 
 #[derive(Component)]
-pub struct Projectile {
-	pub owner: Option<Entity>,
+pub struct Projectile;
+
+/// Who fired the projectile. Removed when the owner despawns or the projectile is released
+#[derive(Component)]
+#[relationship(relationship_target = Shots)]
+pub struct ShotBy(pub Entity);
+
+/// Live projectiles fired by this entity
+#[derive(Component, Deref)]
+#[relationship_target(relationship = ShotBy)]
+pub struct Shots(Vec<Entity>);
+
+/// Projectiles are released after flying too long or too far
+#[derive(Component, Clone, Copy)]
+pub struct ProjectileLifetime {
+	pub time_left: f32,
+	pub distance_left: f32,
+}
+
+impl Default for ProjectileLifetime {
+	fn default() -> Self {
+		Self {
+			time_left: PROJECTILE_LIFETIME,
+			distance_left: PROJECTILE_RANGE,
+		}
+	}
+}
+
+/// Released projectiles are disabled and kept here to be reused by `spawn_projectiles`,
+/// so big chain reactions don't allocate entities and look up assets every tick
+#[derive(Resource, Default)]
+pub struct ProjectilePool {
+	free: Vec<Entity>,
+	sprites: HashMap<Rumor, Handle<Image>>,
+}
+
+/// Use instead of despawning a projectile: `cmds.entity(e).queue(release_projectile)`
+pub fn release_projectile(mut entity: EntityWorldMut) {
+	// can be released twice in the same tick, e.g. hitting two traders at once
+	if entity.contains::<Disabled>() {
+		return;
+	}
+	let link = entity.get::<ChainLink>().copied();
+	entity
+		.insert(Disabled)
+		.remove::<(ProjectileSteering, ShotBy, ChainLink)>();
+	let id = entity.id();
+	entity.world_scope(|world| {
+		if let Some(link) = link {
+			world
+				.resource_mut::<ChainTracker>()
+				.projectile_released(link);
+		}
+		world.resource_mut::<Contacts>().forget(id);
+		world.resource_mut::<ProjectilePool>().free.push(id);
+	});
 }
 
 #[derive(Event)]
@@ -42,6 +133,12 @@ pub struct PlayerShootingLogic {
 	pub timer: Timer,
 	pub tacos_left: u32,
 	pub max_tacos: u32,
+	/// Seconds the button has been held, while charging a shot
+	pub charging: Option<f32>,
+	/// Rumor fired on click, one of `PLAYER_RUMORS`
+	pub rumor: Rumor,
+	/// Flight behavior of the player tacos, set by the `HomingShot` pickup
+	pub steering: Option<ProjectileSteering>,
 }
 
 impl Default for PlayerShootingLogic {
@@ -50,6 +147,9 @@ impl Default for PlayerShootingLogic {
 			timer: Timer::from_seconds(TACO_CHARGE_TIME, TimerMode::Repeating),
 			tacos_left: MAX_TACOS,
 			max_tacos: MAX_TACOS,
+			charging: None,
+			rumor: Rumor::Taco,
+			steering: None,
 		}
 	}
 }
@@ -58,6 +158,7 @@ pub trait BulletPattern {
 	fn direction_iter(self, reference_dir: Vec2) -> impl Iterator<Item = Vec2>;
 }
 
+#[derive(Clone, Copy)]
 pub struct UniformPattern {
 	pub bullet_count: u32,
 }
@@ -72,6 +173,163 @@ impl BulletPattern for UniformPattern {
 	}
 }
 
+/// Bullets spread evenly over `spread` radians, centered on the reference direction
+#[derive(Clone, Copy)]
+pub struct ConePattern {
+	pub bullet_count: u32,
+	pub spread: f32,
+}
+
+impl BulletPattern for ConePattern {
+	fn direction_iter(self, reference_dir: Vec2) -> impl Iterator<Item = Vec2> {
+		let angle_step = self.spread / (self.bullet_count.max(2) - 1) as f32;
+		let start = if self.bullet_count > 1 {
+			-self.spread / 2.
+		} else {
+			0.
+		};
+		(0..self.bullet_count).map(move |i| {
+			let angle = start + i as f32 * angle_step;
+			Vec2::from_angle(angle).rotate(reference_dir)
+		})
+	}
+}
+
+/// Uniform pattern rotated by `offset`, which grows by `step` after every shot
+#[derive(Clone, Copy)]
+pub struct SpiralPattern {
+	pub bullet_count: u32,
+	pub offset: f32,
+	pub step: f32,
+}
+
+impl BulletPattern for SpiralPattern {
+	fn direction_iter(self, reference_dir: Vec2) -> impl Iterator<Item = Vec2> {
+		UniformPattern {
+			bullet_count: self.bullet_count,
+		}
+		.direction_iter(Vec2::from_angle(self.offset).rotate(reference_dir))
+	}
+}
+
+/// Uniform pattern where each bullet is rotated randomly by up to `jitter` radians
+#[derive(Clone, Copy)]
+pub struct JitterPattern {
+	pub bullet_count: u32,
+	pub jitter: f32,
+}
+
+impl BulletPattern for JitterPattern {
+	fn direction_iter(self, reference_dir: Vec2) -> impl Iterator<Item = Vec2> {
+		UniformPattern {
+			bullet_count: self.bullet_count,
+		}
+		.direction_iter(reference_dir)
+		.map(move |dir| {
+			Vec2::from_angle(rand::random_range(-self.jitter..=self.jitter)).rotate(dir)
+		})
+	}
+}
+
+/// A single bullet that bounces off the hit surface. The reference direction points into the surface
+#[derive(Clone, Copy)]
+pub struct ReflectPattern {
+	pub incoming: Vec2,
+}
+
+impl BulletPattern for ReflectPattern {
+	fn direction_iter(self, reference_dir: Vec2) -> impl Iterator<Item = Vec2> {
+		std::iter::once(
+			self.incoming
+				.normalize_or(-reference_dir)
+				.reflect(-reference_dir),
+		)
+	}
+}
+
+/// How projectiles split when they hit a trader. Picked from data for each trader or rumor
+#[derive(Component, Clone, Copy)]
+pub enum SplitPattern {
+	Uniform(UniformPattern),
+	Cone(ConePattern),
+	Spiral(SpiralPattern),
+	Jitter(JitterPattern),
+	/// Reflects the incoming projectile
+	Reflect,
+}
+
+impl Default for SplitPattern {
+	fn default() -> Self {
+		Self::Uniform(UniformPattern { bullet_count: 3 })
+	}
+}
+
+impl SplitPattern {
+	/// Directions of the bullets spawned by a hit. Patterns with state, like the spiral, advance on each call.
+	/// `extra_bullets` come from charged shots, the reflect pattern ignores them
+	pub fn split(&mut self, hit_direction: Vec2, incoming: Vec2, extra_bullets: u32) -> Vec<Vec2> {
+		match self {
+			SplitPattern::Uniform(p) => UniformPattern {
+				bullet_count: p.bullet_count + extra_bullets,
+			}
+			.direction_iter(hit_direction)
+			.collect(),
+			SplitPattern::Cone(p) => ConePattern {
+				bullet_count: p.bullet_count + extra_bullets,
+				..*p
+			}
+			.direction_iter(hit_direction)
+			.collect(),
+			SplitPattern::Spiral(p) => {
+				let directions = SpiralPattern {
+					bullet_count: p.bullet_count + extra_bullets,
+					..*p
+				}
+				.direction_iter(hit_direction)
+				.collect();
+				p.offset += p.step;
+				directions
+			}
+			SplitPattern::Jitter(p) => JitterPattern {
+				bullet_count: p.bullet_count + extra_bullets,
+				..*p
+			}
+			.direction_iter(hit_direction)
+			.collect(),
+			SplitPattern::Reflect => ReflectPattern { incoming }
+				.direction_iter(hit_direction)
+				.collect(),
+		}
+	}
+
+	/// Same pattern without random parts, for previews
+	pub fn deterministic(self) -> Self {
+		match self {
+			SplitPattern::Jitter(p) => SplitPattern::Uniform(UniformPattern {
+				bullet_count: p.bullet_count,
+			}),
+			p => p,
+		}
+	}
+}
+
+/// Split patterns traders can have, picked at random when spawning
+pub const TRADER_SPLIT_PATTERNS: [SplitPattern; 6] = [
+	SplitPattern::Uniform(UniformPattern { bullet_count: 3 }),
+	SplitPattern::Uniform(UniformPattern { bullet_count: 3 }),
+	SplitPattern::Uniform(UniformPattern { bullet_count: 3 }),
+	SplitPattern::Cone(ConePattern {
+		bullet_count: 3,
+		spread: std::f32::consts::FRAC_PI_2,
+	}),
+	SplitPattern::Spiral(SpiralPattern {
+		bullet_count: 3,
+		offset: 0.,
+		step: 0.4,
+	}),
+	SplitPattern::Reflect,
+];
+
 pub struct ShootingPlugin {}
 
 impl Plugin for ShootingPlugin {
@@ -82,65 +340,221 @@ impl Plugin for ShootingPlugin {
 	}
 }
 
+impl PlayerShootingLogic {
+	/// Charge of the shot if the button was released now
+	pub fn charge(&self) -> ShotCharge {
+		let held = self.charging.unwrap_or(0.);
+		ShotCharge((1 + (held / CHARGE_TIME_PER_TACO) as u32).min(self.tacos_left.max(1)))
+	}
+}
+
 // Too big. Should break up
 pub fn player_shooting(
 	mouse_button: Res<ButtonInput<MouseButton>>,
 	touch_res: Res<Touches>,
+	time: Res<Time>,
 	mut spawn_events: EventWriter<SpawnProjectile>,
 	mut gizmos: Gizmos<DottedGizmoConfig>,
 	window: Single<&Window>,
 	camera: Single<(&Camera, &GlobalTransform)>,
-	player: Single<(&Transform, Entity), With<Player>>,
+	player: Single<(&Transform, Entity, Has<TripleShot>), With<Player>>,
 	mut shoot_logic: Single<&mut PlayerShootingLogic>,
-	mut arrow: Single<&mut Transform, (With<PlayerArrowIndicator>, Without<Player>)>, // should move to input or remove
+	mut arrow: Single<
+		&mut Transform,
+		(
+			With<PlayerArrowIndicator>,
+			Without<Player>,
+			Without<Collider>,
+		),
+	>, // should move to input or remove
+	traders: Query<(Entity, &Trader, Has<StatusLock>)>,
+	trader_colliders: Query<(&Transform, &Collider), With<Trader>>,
+	obstacles: Query<&Obstacle>,
+	spatial_query: SpatialQuery,
+	bank_shots: Res<BankShots>,
 	mut cmds: Commands,
 ) {
 	// draw shooting line
-	let touches = touch_res.iter().collect::<Vec<_>>();
-	let target_pos_viewport = if !touches.is_empty() {
-		// touch ongoing
-		touches[0].position()
-	} else if touch_res.any_just_released() {
-		// touch just released
-		touch_res.iter_just_released().next().unwrap().position()
-	} else if let Some(cursor) = window.cursor_position() {
-		// mouse
-		cursor
-	} else {
+	let Some(cursor_pos) = aim_position(&touch_res, &window, *camera) else {
 		return;
 	};
 
-	let cursor_pos = camera
-		.0
-		.viewport_to_world_2d(camera.1, target_pos_viewport)
-		.unwrap();
-
 	// not sure if should use arrow or gizmo line. keeping both for now
 	let start_pos: Vec2 = player.0.translation.xy();
+	let Ok(dir) = Dir2::new(cursor_pos - start_pos) else {
+		return;
+	};
+	// stop the line at the first trader the selected rumor would change, like `handle_collisions`
+	let filter = CastFilter {
+		mask: CollisionLayer::TRADER | CollisionLayer::OBSTACLE,
+		excluded: traders
+			.iter()
+			.filter(|(_, t, locked)| {
+				(*locked && !shoot_logic.rumor.kind().market_wide)
+					|| !shoot_logic.rumor.affects(t.status)
+			})
+			.map(|(e, ..)| e)
+			.collect(),
+	};
+	let max_distance = Vec2::new(WIDTH, HEIGHT).length() * 2.;
+	let charge = shoot_logic.charge();
+	let hit = spatial_query.cast_circle(start_pos, charge.radius(), dir, max_distance, &filter);
+	let line_end = hit.map_or(cursor_pos, |hit| start_pos + dir * hit.distance);
 	gizmos.line_2d(
 		start_pos,
-		cursor_pos,
+		line_end,
 		bevy::color::palettes::css::YELLOW.with_alpha(0.5),
 	);
+	if let Some(hit) = hit {
+		gizmos.circle_2d(
+			Isometry2d::from_translation(hit.point),
+			4.,
+			bevy::color::palettes::css::YELLOW.with_alpha(0.8),
+		);
+		// show where a stall would send it
+		if obstacles
+			.get(hit.entity)
+			.is_ok_and(|o| o.projectile_response == ProjectileResponse::Reflect)
+		{
+			gizmos.line_2d(
+				line_end,
+				line_end + dir.reflect(hit.normal) * 60.,
+				bevy::color::palettes::css::YELLOW.with_alpha(0.3),
+			);
+		}
+	}
+	if let Some((transform, collider)) = hit.and_then(|hit| trader_colliders.get(hit.entity).ok()) {
+		gizmos.circle_2d(
+			Isometry2d::from_translation(transform.translation.xy() + collider.offset),
+			collider.radius + 5.,
+			bevy::color::palettes::css::YELLOW.with_alpha(0.8),
+		);
+	}
 	const ARROW_DISTANCE: f32 = 100.;
-	let dir = (cursor_pos - start_pos).normalize();
 	arrow.translation = (start_pos + dir * ARROW_DISTANCE).extend(900.);
 	arrow.rotation = Quat::from_rotation_z(dir.to_angle());
 
-	// fire taco
+	// charge while held, fire taco on release
 	if shoot_logic.tacos_left == 0 {
+		shoot_logic.charging = None;
+		return;
+	}
+	if mouse_button.just_pressed(MouseButton::Left) || touch_res.any_just_pressed() {
+		shoot_logic.charging = Some(0.);
+	}
+	let Some(held) = shoot_logic.charging.map(|held| held + time.delta_secs()) else {
+		return;
+	};
+	shoot_logic.charging = Some(held);
+	let charge = shoot_logic.charge();
+	if !mouse_button.just_released(MouseButton::Left) && !touch_res.any_just_released() {
+		// progress to a full charge along the aim line, one ring per taco
+		let full_charge = CHARGE_TIME_PER_TACO * (shoot_logic.tacos_left - 1) as f32;
+		let progress = if full_charge > 0. {
+			(held / full_charge).min(1.)
+		} else {
+			1.
+		};
+		gizmos.line_2d(
+			start_pos,
+			start_pos.lerp(line_end, progress),
+			bevy::color::palettes::css::ORANGE.with_alpha(0.8),
+		);
+		for i in 0..charge.0 {
+			gizmos.circle_2d(
+				Isometry2d::from_translation(start_pos),
+				charge.radius() + 6. * i as f32,
+				bevy::color::palettes::css::ORANGE.with_alpha(0.6),
+			);
+		}
 		return;
 	}
-	if mouse_button.just_pressed(MouseButton::Left) || touch_res.any_just_released() {
+	for angle in shot_angles(player.2) {
 		spawn_events.write(SpawnProjectile {
-			projectile_type: Rumor::Taco,
+			projectile_type: shoot_logic.rumor,
 			position: start_pos,
-			direction: (cursor_pos - start_pos).normalize() * PROJECTILE_SPEED,
-			owner: None,
+			direction: Vec2::from_angle(*angle).rotate(*dir) * shoot_logic.rumor.kind().speed,
+			owner: Some(player.1),
+			edge_behavior: bank_shots.edge_behavior(),
+			steering: shoot_logic.steering,
+			chain: None,
+			charge,
 		});
-		shoot_logic.tacos_left -= 1;
-		cmds.trigger_targets(RumorJustShot, player.1);
 	}
+	shoot_logic.tacos_left -= charge.0;
+	shoot_logic.charging = None;
+	cmds.trigger_targets(RumorJustShot, player.1);
+}
+
+/// Angles from the aim direction of the tacos fired by one shot
+pub fn shot_angles(triple_shot: bool) -> &'static [f32] {
+	const TRIPLE_SHOT_ANGLE: f32 = 0.25;
+	if triple_shot {
+		&[-TRIPLE_SHOT_ANGLE, 0., TRIPLE_SHOT_ANGLE]
+	} else {
+		&[0.]
+	}
+}
+
+/// Number keys pick the rumor directly, the mouse wheel cycles through them
+pub fn select_player_rumor(
+	key_input: Res<ButtonInput<KeyCode>>,
+	scroll: Res<bevy::input::mouse::AccumulatedMouseScroll>,
+	player: Single<(Entity, &mut PlayerShootingLogic)>,
+	mut overhead_events: EventWriter<OverheadTextRequest>,
+) {
+	const KEYS: [KeyCode; 4] = [
+		KeyCode::Digit1,
+		KeyCode::Digit2,
+		KeyCode::Digit3,
+		KeyCode::Digit4,
+	];
+	let (entity, mut shoot_logic) = player.into_inner();
+	let current = PLAYER_RUMORS
+		.iter()
+		.position(|r| *r == shoot_logic.rumor)
+		.unwrap_or_default();
+	let selected = if let Some(i) = KEYS.iter().position(|k| key_input.just_pressed(*k)) {
+		i
+	} else if scroll.delta.y > 0. {
+		(current + 1) % PLAYER_RUMORS.len()
+	} else if scroll.delta.y < 0. {
+		(current + PLAYER_RUMORS.len() - 1) % PLAYER_RUMORS.len()
+	} else {
+		return;
+	};
+	if selected == current {
+		return;
+	}
+	shoot_logic.rumor = PLAYER_RUMORS[selected];
+	overhead_events.write(OverheadTextRequest {
+		attached_to: entity,
+		text: Some(shoot_logic.rumor.kind().name.to_string()),
+		duration_sec: Some(1.),
+	});
+}
+
+/// World position the player is aiming at, with touch or mouse
+pub fn aim_position(
+	touch_res: &Touches,
+	window: &Window,
+	(camera, camera_transform): (&Camera, &GlobalTransform),
+) -> Option<Vec2> {
+	let touches = touch_res.iter().collect::<Vec<_>>();
+	let target_pos_viewport = if !touches.is_empty() {
+		// touch ongoing
+		touches[0].position()
+	} else if touch_res.any_just_released() {
+		// touch just released
+		touch_res.iter_just_released().next().unwrap().position()
+	} else {
+		// mouse
+		window.cursor_position()?
+	};
+
+	camera
+		.viewport_to_world_2d(camera_transform, target_pos_viewport)
+		.ok()
 }
 
 pub fn charge_player_tacos(mut q: Single<&mut PlayerShootingLogic>, time: Res<Time>) {
@@ -153,63 +567,168 @@ pub fn charge_player_tacos(mut q: Single<&mut PlayerShootingLogic>, time: Res<Ti
 }
 
 pub fn handle_timed_shooting(
-	query: Query<(&Transform, Entity, &mut TimedShooter)>,
-	traders_q: Query<&Transform, With<Trader>>,
+	query: Query<(&Transform, Entity, &mut TimedShooter, Has<Antagonist>), Without<Silenced>>,
+	traders_q: Query<(&Transform, &PhysicsBody, &Trader)>,
+	player: Single<&Transform, With<Player>>,
 	time: Res<Time>,
+	stats: Res<GameStats>,
+	curve: Res<DifficultyCurve>,
+	mut chains: ResMut<ChainTracker>,
 	mut spawn_events: EventWriter<SpawnProjectile>,
 	mut cmds: Commands,
 ) {
-	for (transform, entity, mut shooter) in query {
-		if !shooter.shooting_timer.tick(time.delta()).just_finished() {
+	let curve_scale = curve.at(&stats);
+	for (transform, entity, mut shooter, antagonist) in query {
+		// the auto-shooting player of the menu keeps its pace
+		let scale = if antagonist {
+			curve_scale
+		} else {
+			DifficultyScale::default()
+		};
+		if !shooter
+			.shooting_timer
+			.tick(time.delta().mul_f32(scale.fire_rate))
+			.just_finished()
+		{
 			continue;
 		}
-		use rand::seq::IteratorRandom;
-		let mut rng = rand::rng();
-		let direction = traders_q
-			.iter()
-			.choose(&mut rng)
-			.map(|trader| (trader.translation.xy() - transform.translation.xy()).normalize())
-			.unwrap_or(Vec2::new(0., -1.));
-
-		spawn_events.write(SpawnProjectile {
-			projectile_type: shooter.shot_type,
-			position: transform.translation.xy(),
-			direction: direction * PROJECTILE_SPEED,
-			owner: Some(entity),
-		});
 		cmds.trigger_targets(RumorJustShot, entity);
+		// a burst is one chain, so its shots don't interact with each other at the muzzle
+		let volley = (scale.burst() > 1).then(|| chains.start(Some(entity), time.elapsed_secs()));
+		for _ in 0..scale.burst() {
+			let Some(shot) = shoot_at_traders(
+				transform.translation.xy(),
+				entity,
+				&shooter,
+				&traders_q,
+				player.translation.xy(),
+				scale.projectile_speed,
+			) else {
+				continue;
+			};
+			spawn_events.write(SpawnProjectile {
+				chain: volley,
+				..shot
+			});
+		}
 	}
 }
 
-pub fn on_donnie_shot(
-	_: Trigger<RumorJustShot>,
-	mut q: Single<(Entity, &mut Sprite), With<Donnie>>,
+/// Each shot of a burst picks its own rumor and target
+fn shoot_at_traders(
+	origin: Vec2,
+	entity: Entity,
+	shooter: &TimedShooter,
+	traders_q: &Query<(&Transform, &PhysicsBody, &Trader)>,
+	player: Vec2,
+	speed_scale: f32,
+) -> Option<SpawnProjectile> {
+	use rand::seq::IndexedRandom;
+	let mut rng = rand::rng();
+	let &shot_type = shooter.shot_types.choose(&mut rng)?;
+	let speed = shot_type.kind().speed * speed_scale;
+
+	let candidates = traders_q
+		.iter()
+		.map(|(t, body, trader)| TargetCandidate {
+			position: t.translation.xy(),
+			velocity: body.velocity,
+			status: trader.status,
+		})
+		.collect::<Vec<_>>();
+	// prefer the traders the rumor would change
+	let affected = candidates
+		.iter()
+		.copied()
+		.filter(|c| shot_type.affects(c.status))
+		.collect::<Vec<_>>();
+	let candidates = if affected.is_empty() {
+		candidates
+	} else {
+		affected
+	};
+	let direction = shooter
+		.targeting
+		.choose(&mut rng)
+		.and_then(|strategy| strategy.aim(origin, speed, &candidates, player))
+		.unwrap_or(Vec2::new(0., -1.));
+
+	Some(SpawnProjectile {
+		projectile_type: shot_type,
+		position: origin,
+		direction: direction * speed,
+		owner: Some(entity),
+		edge_behavior: EdgeBehavior::Destroy,
+		steering: shooter.steering.choose(&mut rng).copied(),
+		chain: None,
+		charge: default(),
+	})
+}
+
+pub fn expire_projectiles(
+	mut query: Query<(Entity, &mut ProjectileLifetime, &PhysicsBody)>,
+	time: Res<Time>,
+	mut cmds: Commands,
+) {
+	for (entity, mut lifetime, body) in query.iter_mut() {
+		lifetime.time_left -= time.delta_secs();
+		lifetime.distance_left -= body.velocity.length() * time.delta_secs();
+		if lifetime.time_left <= 0. || lifetime.distance_left <= 0. {
+			cmds.entity(entity).queue(release_projectile);
+		}
+	}
+}
+
+pub fn on_antagonist_shot(
+	trigger: Trigger<RumorJustShot>,
+	mut antagonists: Query<(&Antagonist, &mut Sprite)>,
 	mut overhead_events: EventWriter<OverheadTextRequest>,
 	asset_server: Res<AssetServer>,
 ) {
-	let (entity, sprite) = (q.0, &mut q.1);
+	let Ok((antagonist, mut sprite)) = antagonists.get_mut(trigger.target()) else {
+		return;
+	};
 	overhead_events.write(OverheadTextRequest {
-		attached_to: entity,
-		text: Some(random_tariff()),
+		attached_to: trigger.target(),
+		text: Some((antagonist.kind.shout)()),
 		duration_sec: Some(1.5),
 	});
-	sprite.image = asset_server.load(donnie_texture_path());
+	sprite.image = asset_server.load((antagonist.kind.sprite)());
 }
 
 pub fn spawn_projectiles(
 	mut spawn_events: EventReader<SpawnProjectile>,
 	mut commands: Commands,
 	asset_server: Res<AssetServer>,
+	mut pool: ResMut<ProjectilePool>,
+	mut chains: ResMut<ChainTracker>,
 	mut stats: ResMut<GameStats>,
+	time: Res<Time>,
 ) {
+	let pool = pool.as_mut();
 	for event in spawn_events.read() {
-		commands.spawn((
+		let link = event
+			.chain
+			.unwrap_or_else(|| chains.start(event.owner, time.elapsed_secs()));
+		chains.projectile_spawned(link);
+		let image = pool
+			.sprites
+			.entry(event.projectile_type)
+			.or_insert_with(|| asset_server.load(event.projectile_type.kind().sprite))
+			.clone();
+		// every component is overwritten when reusing
+		let mut projectile = match pool.free.pop() {
+			Some(entity) => {
+				let mut projectile = commands.entity(entity);
+				projectile.remove::<Disabled>();
+				projectile
+			}
+			None => commands.spawn_empty(),
+		};
+		projectile.insert((
 			Sprite {
-				image: asset_server.load(match event.projectile_type {
-					Rumor::Tariff => "pile-of-poo-svgrepo-com.png",
-					Rumor::Taco => "taco_man3/taco.png",
-				}),
-				custom_size: Some(vec2(50., 50.)),
+				image,
+				custom_size: Some(Vec2::splat(50. * event.charge.radius() / PROJECTILE_RADIUS)),
 				..Default::default()
 			},
 			event.projectile_type,
@@ -218,22 +737,36 @@ pub fn spawn_projectiles(
 				..Default::default()
 			},
 			Collider {
-				radius: 20.,
+				radius: event.charge.radius(),
 				offset: Vec2::ZERO,
+				layer: CollisionLayer::PROJECTILE,
+				mask: CollisionLayer::TRADER
+					| CollisionLayer::OBSTACLE
+					| CollisionLayer::PROJECTILE,
 			},
 			PhysicsBody {
 				velocity: event.direction,
 				..Default::default()
 			},
-			EdgeBehavior::Destroy,
-			Projectile { owner: event.owner },
+			event.edge_behavior,
+			Projectile,
+			link,
+			event.charge,
+			ProjectileLifetime::default(),
 			AreaTrigger,
+			ContinuousCollision,
 			Animation::<Transform> {
 				progress: 0.,
 				animation_speed: 1.,
 				animations: vec![AnimValue::new(|t, _, n| t.rotate_local_z(n), |_| 0.1)],
 			},
 		));
+		if let Some(steering) = event.steering {
+			projectile.insert(steering);
+		}
+		if let Some(owner) = event.owner {
+			projectile.insert(ShotBy(owner));
+		}
 		stats.total_projectiles_launched += 1;
 	}
 }
@@ -244,7 +777,8 @@ fn remove_player_auto_shooting(player_q: Single<Entity, With<Player>>, mut cmds:
 
 fn add_player_auto_shooting(player_q: Single<Entity, With<Player>>, mut cmds: Commands) {
 	cmds.entity(player_q.entity()).insert(TimedShooter {
-		shot_type: Rumor::Taco,
+		shot_types: vec![Rumor::Taco],
+		steering: vec![ProjectileSteering::homing()],
 		..default()
 	});
 }
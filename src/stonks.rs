@@ -9,9 +9,30 @@ pub struct StonksTrading {
 	pub returns_total: i64,
 	pub price_history: VecDeque<u32>,
 	pub phase: TradePhase,
+	/// Price if every trader was bearish. Changes with the population
+	pub price_lowest: u32,
+	/// Price if every trader was bullish
+	pub price_highest: u32,
 }
 
 impl StonksTrading {
+	/// 0 at `price_lowest`, 1 at `price_highest`
+	pub fn price_ratio(&self, price: u32) -> f32 {
+		if self.price_highest <= self.price_lowest {
+			return 0.5;
+		}
+		(price as f32 - self.price_lowest as f32) / (self.price_highest - self.price_lowest) as f32
+	}
+
+	fn notif_thresholds(&self) -> (u32, u32) {
+		const NOTIF_THRESHOLD: f32 = 0.7;
+		let diff = (self.price_highest - self.price_lowest) as f32;
+		(
+			(diff * (1. - NOTIF_THRESHOLD)) as u32 + self.price_lowest,
+			(diff * NOTIF_THRESHOLD) as u32 + self.price_lowest,
+		)
+	}
+
 	pub fn avg_buy_price(&self) -> Option<u32> {
 		if self.owned != 0 {
 			Some(self.spent / self.owned)
@@ -39,20 +60,23 @@ pub enum StonksPriceNotification {
 
 pub fn update_stonks_price(
 	mut stonks: ResMut<StonksTrading>,
-	query: Query<&Trader>,
+	query: Query<(&Trader, &Archetype)>,
 	mut cmds: Commands,
 ) {
 	let counts = query
 		.iter()
-		.map(|t| t.status)
-		.fold([0, 0, 0], |mut c, status| {
-			c[status as usize] += 1;
+		.map(|(t, a)| (t.status, a.kind().price_weight))
+		.fold([0, 0, 0], |mut c, (status, weight)| {
+			c[status as usize] += weight;
 			c
 		});
+	let total_weight = counts.iter().sum::<u32>();
 	let price_current = STONKS_PER_NEUTRAL * counts[TraderStatus::Neutral as usize]
 		+ STONKS_PER_BEARISH * counts[TraderStatus::Bearish as usize]
 		+ STONKS_PER_BULLISH * counts[TraderStatus::Bullish as usize];
 	stonks.price_current = price_current;
+	stonks.price_lowest = STONKS_PER_BEARISH * total_weight;
+	stonks.price_highest = STONKS_PER_BULLISH * total_weight;
 
 	if stonks.price_history.len() > STONKS_DATA_POINTS as usize {
 		stonks.price_history.pop_front();
@@ -61,7 +85,7 @@ pub fn update_stonks_price(
 	let price_prev = *stonks.price_history.back().unwrap_or(&0);
 	stonks.price_history.push_back(price_current);
 
-	let (low, high) = notif_thresholds();
+	let (low, high) = stonks.notif_thresholds();
 	if price_current <= low && price_prev > low {
 		cmds.trigger(StonksPriceNotification::LOW);
 	}
@@ -70,15 +94,6 @@ pub fn update_stonks_price(
 	}
 }
 
-const fn notif_thresholds() -> (u32, u32) {
-	const NOTIF_THRESHOLD: f32 = 0.7;
-	const DIFF: f32 = PRICE_HIGHEST - PRICE_LOWEST;
-	(
-		(DIFF * (1. - NOTIF_THRESHOLD) + PRICE_LOWEST) as u32,
-		(DIFF * NOTIF_THRESHOLD + PRICE_LOWEST) as u32,
-	)
-}
-
 pub fn player_investing(
 	key_input: Res<ButtonInput<KeyCode>>,
 	touch_res: Res<Touches>,
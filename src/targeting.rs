@@ -0,0 +1,95 @@
+use crate::*;
+use rand::seq::IndexedRandom;
+
+/// How a `TimedShooter` picks where to fire
+#[derive(Clone, Copy, PartialEq)]
+pub enum TargetingStrategy {
+	Random,
+	/// The bullish trader with the most bullish neighbours
+	BullishCluster,
+	Nearest,
+	/// Nearest trader, aiming where it will be when the projectile gets there
+	Lead,
+	/// The trader closest to the taco truck
+	NearPlayer,
+}
+
+#[derive(Clone, Copy)]
+pub struct TargetCandidate {
+	pub position: Vec2,
+	pub velocity: Vec2,
+	pub status: TraderStatus,
+}
+
+/// Distance at which bullish traders count as the same cluster
+const CLUSTER_RADIUS: f32 = 120.;
+
+impl TargetingStrategy {
+	/// Direction to fire in from `origin`, None when there is nothing to aim at
+	pub fn aim(
+		self,
+		origin: Vec2,
+		speed: f32,
+		candidates: &[TargetCandidate],
+		player: Vec2,
+	) -> Option<Vec2> {
+		let nearest_to = |point: Vec2| {
+			candidates.iter().min_by(|a, b| {
+				a.position
+					.distance_squared(point)
+					.total_cmp(&b.position.distance_squared(point))
+			})
+		};
+		let target = match self {
+			TargetingStrategy::Random => candidates.choose(&mut rand::rng())?.position,
+			TargetingStrategy::BullishCluster => {
+				let bullish = candidates
+					.iter()
+					.filter(|c| c.status == TraderStatus::Bullish);
+				let neighbours = |c: &TargetCandidate| {
+					bullish
+						.clone()
+						.filter(|other| other.position.distance(c.position) < CLUSTER_RADIUS)
+						.count()
+				};
+				// no bullish trader, anyone will do
+				match bullish.clone().max_by_key(|c| neighbours(c)) {
+					Some(densest) => densest.position,
+					None => nearest_to(origin)?.position,
+				}
+			}
+			TargetingStrategy::Nearest => nearest_to(origin)?.position,
+			TargetingStrategy::Lead => {
+				let target = nearest_to(origin)?;
+				intercept(origin, speed, target.position, target.velocity)
+					.unwrap_or(target.position)
+			}
+			TargetingStrategy::NearPlayer => nearest_to(player)?.position,
+		};
+		(target - origin).try_normalize()
+	}
+}
+
+/// Where a projectile fired from `origin` at `speed` meets a target moving at constant velocity
+fn intercept(origin: Vec2, speed: f32, position: Vec2, velocity: Vec2) -> Option<Vec2> {
+	// |offset + velocity * t| = speed * t
+	let offset = position - origin;
+	let a = velocity.length_squared() - speed * speed;
+	let b = 2. * offset.dot(velocity);
+	let c = offset.length_squared();
+	let t = if a.abs() < f32::EPSILON {
+		-c / b
+	} else {
+		let discriminant = b * b - 4. * a * c;
+		if discriminant < 0. {
+			return None;
+		}
+		let sqrt = discriminant.sqrt();
+		// smallest positive root
+		[(-b - sqrt) / (2. * a), (-b + sqrt) / (2. * a)]
+			.into_iter()
+			.filter(|t| *t > 0.)
+			.reduce(f32::min)?
+	};
+	(t > 0.).then(|| position + velocity * t)
+}
@@ -8,9 +8,158 @@ pub enum TraderStatus {
 	Bearish,
 }
 
+/// What a trader wants to move towards
+#[derive(Clone, Copy)]
+pub enum MovementGoal {
+	Wander,
+	/// Towards the nearest border of the arena, stopping short of it
+	Edges,
+	/// Close to the taco truck, but not on top of it
+	Player,
+}
+
+/// How a status changes the way traders move, read by `handle_random_movement` and `handle_flocking`
+pub struct StatusBehavior {
+	/// Multiplier of the movement speed
+	pub speed: f32,
+	pub goal: MovementGoal,
+	/// 0 ignores the goal, 1 heads straight for it
+	pub goal_weight: f32,
+	pub flock: FlockWeights,
+}
+
+const NEUTRAL_BEHAVIOR: StatusBehavior = StatusBehavior {
+	speed: 1.,
+	goal: MovementGoal::Wander,
+	goal_weight: 0.,
+	flock: FlockWeights {
+		separation: 1.5,
+		alignment: 1.,
+		cohesion: 1.,
+		wander: 0.5,
+	},
+};
+
+/// Bullish traders slow down and huddle around the taco truck
+const BULLISH_BEHAVIOR: StatusBehavior = StatusBehavior {
+	speed: 0.6,
+	goal: MovementGoal::Player,
+	goal_weight: 0.7,
+	flock: FlockWeights {
+		separation: 1.,
+		alignment: 1.5,
+		cohesion: 2.5,
+		wander: 0.3,
+	},
+};
+
+/// Panicking bearish traders scatter towards the edges
+const BEARISH_BEHAVIOR: StatusBehavior = StatusBehavior {
+	speed: 1.6,
+	goal: MovementGoal::Edges,
+	goal_weight: 0.6,
+	flock: FlockWeights {
+		separation: 4.,
+		alignment: 0.,
+		cohesion: -1.,
+		wander: 1.5,
+	},
+};
+
+/// Bullish traders stop pulling towards the taco truck this close to it
+const GATHER_DISTANCE: f32 = 100.;
+/// Bearish traders stop pulling towards the border this close to it, otherwise they wrap around
+const EDGE_MARGIN: f32 = 60.;
+
+impl TraderStatus {
+	pub fn behavior(self) -> &'static StatusBehavior {
+		match self {
+			TraderStatus::Neutral => &NEUTRAL_BEHAVIOR,
+			TraderStatus::Bullish => &BULLISH_BEHAVIOR,
+			TraderStatus::Bearish => &BEARISH_BEHAVIOR,
+		}
+	}
+}
+
+impl StatusBehavior {
+	/// Unit vector towards the goal, zero when there is nowhere to go
+	pub fn goal_direction(&self, position: Vec2, player: Vec2) -> Vec2 {
+		match self.goal {
+			MovementGoal::Wander => Vec2::ZERO,
+			MovementGoal::Edges => {
+				// towards the nearest border
+				let to_border = Vec2::new(WIDTH, HEIGHT) - position.abs();
+				if to_border.min_element() < EDGE_MARGIN {
+					Vec2::ZERO
+				} else if to_border.x < to_border.y {
+					Vec2::new(position.x.signum(), 0.)
+				} else {
+					Vec2::new(0., position.y.signum())
+				}
+			}
+			MovementGoal::Player if position.distance(player) < GATHER_DISTANCE => Vec2::ZERO,
+			MovementGoal::Player => (player - position).normalize_or_zero(),
+		}
+	}
+
+	/// Already at the goal and still heading further, e.g. about to wrap around the border
+	pub fn overshooting(&self, position: Vec2, velocity: Vec2, player: Vec2) -> bool {
+		match self.goal {
+			MovementGoal::Wander => false,
+			MovementGoal::Edges => {
+				let to_border = Vec2::new(WIDTH, HEIGHT) - position.abs();
+				let outward = position.signum() * velocity;
+				(to_border.x < EDGE_MARGIN && outward.x > 0.)
+					|| (to_border.y < EDGE_MARGIN && outward.y > 0.)
+			}
+			MovementGoal::Player => {
+				position.distance(player) < GATHER_DISTANCE && velocity.dot(player - position) > 0.
+			}
+		}
+	}
+
+	/// Velocity without the part that would carry it past the goal
+	pub fn hold_back(&self, position: Vec2, velocity: Vec2, player: Vec2) -> Vec2 {
+		if !self.overshooting(position, velocity, player) {
+			return velocity;
+		}
+		match self.goal {
+			MovementGoal::Wander => velocity,
+			MovementGoal::Edges => {
+				let to_border = Vec2::new(WIDTH, HEIGHT) - position.abs();
+				let outward = position.signum() * velocity;
+				Vec2::new(
+					if to_border.x < EDGE_MARGIN && outward.x > 0. {
+						0.
+					} else {
+						velocity.x
+					},
+					if to_border.y < EDGE_MARGIN && outward.y > 0. {
+						0.
+					} else {
+						velocity.y
+					},
+				)
+			}
+			MovementGoal::Player => velocity.reject_from(player - position),
+		}
+	}
+
+	/// Bends a random wander velocity towards the goal and scales it
+	pub fn steer(&self, wander: Vec2, position: Vec2, player: Vec2) -> Vec2 {
+		let direction = wander
+			.normalize_or_zero()
+			.lerp(self.goal_direction(position, player), self.goal_weight)
+			.normalize_or_zero();
+		direction * wander.length() * self.speed
+	}
+}
+
 #[derive(Component, Default)]
 pub struct Trader {
 	pub status: TraderStatus,
+	/// Rumors taken since the last status change, see `ArchetypeKind::hits_required`
+	pub hits: u32,
 }
 
 /// Changes the trader status after some time
@@ -18,11 +167,9 @@ pub struct Trader {
 #[require(Trader)]
 pub struct TraderStatusTimer(pub Timer);
 
-/// Rest time for a trader in which it can't collide with projectiles
-/// Avoids chain reactions that flood the game with projectiles
-#[derive(Component, Deref, DerefMut)]
-#[require(Trader)]
-pub struct TraderRestTimer(pub Timer);
+/// Rumors pass through until the `TraderStatusTimer` finishes
+#[derive(Component)]
+pub struct StatusLock;
 
 #[derive(Event, Clone)]
 pub struct TraderChange {
@@ -33,7 +180,7 @@ pub struct TraderChange {
 
 /// Just a graphical update of the sprite and overhead text
 pub fn update_trader_status(
-	mut traders: Query<(&mut Sprite, &Trader, Entity)>,
+	mut traders: Query<(&mut Sprite, &Trader, &Archetype, Entity)>,
 	mut events: EventReader<TraderChange>,
 	mut overhead_events: EventWriter<OverheadTextRequest>,
 	asset_server: Res<AssetServer>,
@@ -43,13 +190,14 @@ pub fn update_trader_status(
 	const TEXT_CHANCE: f64 = 0.5;
 
 	for event in events.read() {
-		let (mut sprite, trader, entity) = traders.get_mut(event.entity).unwrap();
+		// might have gone bankrupt since
+		let Ok((mut sprite, trader, archetype, entity)) = traders.get_mut(event.entity) else {
+			continue;
+		};
+		sprite.image = asset_server.load(archetype.kind().sprites.path(trader.status));
 		match trader.status {
-			TraderStatus::Neutral => {
-				sprite.image = asset_server.load(investor_texture_path());
-			}
+			TraderStatus::Neutral => {}
 			TraderStatus::Bearish => {
-				sprite.image = asset_server.load(bearish_texture_path());
 				cmds.trigger_targets(event.clone(), entity);
 				if rng.random_bool(TEXT_CHANCE) {
 					overhead_events.write(OverheadTextRequest {
@@ -60,7 +208,6 @@ pub fn update_trader_status(
 				}
 			}
 			TraderStatus::Bullish => {
-				sprite.image = asset_server.load(bullish_texture_path());
 				cmds.trigger_targets(event.clone(), entity);
 				if rng.random_bool(TEXT_CHANCE) {
 					overhead_events.write(OverheadTextRequest {
@@ -77,7 +224,6 @@ pub fn update_trader_status(
 pub fn tick_trader_timers(
 	time: Res<Time>,
 	mut query_status: Query<(&mut TraderStatusTimer, &mut Trader, Entity)>,
-	query_rest: Query<(&mut TraderRestTimer, Entity)>,
 	mut trader_changes: EventWriter<TraderChange>,
 	mut cmds: Commands,
 ) {
@@ -89,15 +235,11 @@ pub fn tick_trader_timers(
 				new: TraderStatus::Neutral,
 			};
 			trader.status = TraderStatus::Neutral;
+			trader.hits = 0;
 			trader_changes.write(change_event.clone());
 			cmds.trigger_targets(change_event, entity);
-			cmds.entity(entity).remove::<TraderStatusTimer>();
-		}
-	}
-
-	for (mut timer, entity) in query_rest {
-		if timer.tick(time.delta()).just_finished() {
-			cmds.entity(entity).remove::<TraderRestTimer>();
+			cmds.entity(entity)
+				.remove::<(TraderStatusTimer, StatusLock)>();
 		}
 	}
 }
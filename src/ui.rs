@@ -204,9 +204,10 @@ pub fn ui_update(
 	use bevy::color::palettes::css::*;
 	const HUE_MAX: f32 = 123.;
 
-	fn price_ratio(v: u32) -> f32 {
-		(v as f32 - PRICE_LOWEST) / (PRICE_HIGHEST - PRICE_LOWEST)
-	}
+	// the range follows the population, so the chart is scaled to the current bounds
+	const CHART_MARGIN: f32 = 20.;
+	let price_y =
+		|v: u32| CHART_MARGIN + stonks.price_ratio(v).clamp(0., 1.) * (CHART_SIZE.y - CHART_MARGIN);
 
 	// level border
 	gizmos_dotted.rect_2d(
@@ -218,7 +219,7 @@ pub fn ui_update(
 	// new chart
 	// buy value indicator
 	if let Some(buy_price) = stonks.avg_buy_price() {
-		let buy_value = CHART_OFFSET + Vec2::new(0., buy_price as f32);
+		let buy_value = CHART_OFFSET + Vec2::new(0., price_y(buy_price));
 		gizmos_dotted.line_2d(
 			buy_value,
 			buy_value + Vec2::new(CHART_SIZE.x, 0.),
@@ -234,11 +235,10 @@ pub fn ui_update(
 	);
 	// chart history
 	let x_step = CHART_SIZE.x / STONKS_DATA_POINTS as f32;
-	let y_fact = 1.; // should calc properly
 	gizmos.linestrip_gradient_2d(stonks.price_history.iter().enumerate().map(|(i, &v)| {
 		(
-			CHART_OFFSET + Vec2::new(i as f32 * x_step, v as f32 * y_fact),
-			Hsla::new(price_ratio(v) * HUE_MAX, 0.7, 0.5, 1.),
+			CHART_OFFSET + Vec2::new(i as f32 * x_step, price_y(v)),
+			Hsla::new(stonks.price_ratio(v).clamp(0., 1.) * HUE_MAX, 0.7, 0.5, 1.),
 		)
 	}));
 }